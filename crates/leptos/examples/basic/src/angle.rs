@@ -0,0 +1,38 @@
+use leptos::*;
+use leptos_compare_image::LeptosCompareImage;
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        let (angle, set_angle) = create_signal(30.0);
+
+        view! {
+            <div style="max-width: 640px;">
+                {move || {
+                    view! {
+                        <LeptosCompareImage
+                            angle=Some(angle.get())
+                            left_image=img1_src
+                            left_image_label=Some("Before".to_string())
+                            right_image=img2_src
+                            right_image_label=Some("After".to_string())
+                        />
+                    }
+                }}
+                <input
+                    type="range"
+                    min="-90"
+                    max="90"
+                    prop:value=angle
+                    on:input=move |e| {
+                        set_angle.set(event_target_value(&e).parse().unwrap_or_default());
+                    }
+                />
+            </div>
+        }
+    })
+}
@@ -1,5 +1,5 @@
 use leptos::*;
-use leptos_compare_image::LeptosCompareImage;
+use leptos_compare_image::{LeptosCompareImage, Orientation};
 
 pub fn main() {
     _ = console_log::init_with_level(log::Level::Debug);
@@ -16,7 +16,34 @@ pub fn main() {
                 <br />
 
                 "Vertical comparison"
-                <LeptosCompareImage left_image=img1_src right_image=img2_src vertical=true />
+                <LeptosCompareImage
+                    left_image=img1_src
+                    orientation=Some(Orientation::TopToBottom)
+                    right_image=img2_src
+                />
+
+                <br />
+
+                "Right to left comparison"
+                <LeptosCompareImage
+                    left_image=img1_src
+                    orientation=Some(Orientation::RightToLeft)
+                    right_image=img2_src
+                />
+
+                <br />
+
+                <LeptosCompareImage
+                    caption=Some("Rendering before and after the lighting fix".to_string())
+                    description=Some(
+                        "The shadows on the right are softer and no longer show banding."
+                            .to_string(),
+                    )
+                    left_image=img1_src
+                    left_image_alt="Scene with banded shadows"
+                    right_image=img2_src
+                    right_image_alt="Scene with smooth shadows"
+                />
             </div>
         }
     })
@@ -8,6 +8,7 @@ fn main() {
     let img1_src = "images/image1.png";
     let img2_src = "images/image2.png";
     let (position, set_position) = create_signal(0.5);
+    let (committed, set_committed) = create_signal(0.5);
 
     mount_to_body(move || {
         view! {
@@ -16,8 +17,11 @@ fn main() {
                 left_image={img1_src}
                 right_image={img2_src}
                 on_slider_position_change={Some(Callback::new(move |position| set_position.set(position)))}
+                on_slider_position_commit={Some(Callback::new(move |position| set_committed.set(position)))}
+                position_change_throttle=true
                 />
                 <div>slider position: {position}</div>
+                <div>committed position: {committed}</div>
         </div>
         }
     })
@@ -0,0 +1,32 @@
+use leptos::*;
+use leptos_compare_image::{CompareImageController, ExportOptions, LeptosCompareImage};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+    let controller = CompareImageController::new();
+    let (snapshot, set_snapshot) = create_signal(None::<String>);
+
+    mount_to_body(move || {
+        view! {
+            <div style="max-width: 640px;">
+                <LeptosCompareImage
+                    controller=Some(controller)
+                    left_image=img1_src
+                    left_image_label=Some("Before".to_string())
+                    right_image=img2_src
+                    right_image_label=Some("After".to_string())
+                />
+                <button on:click=move |_| {
+                    match controller.export_data_url(ExportOptions::default()) {
+                        Ok(url) => set_snapshot.set(Some(url)),
+                        Err(err) => log::error!("export failed: {err:?}"),
+                    }
+                }>"Export"</button>
+                {move || snapshot.get().map(|src| view! { <img src=src style="width: 100%;" /> })}
+            </div>
+        }
+    })
+}
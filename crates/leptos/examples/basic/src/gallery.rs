@@ -0,0 +1,27 @@
+use leptos::*;
+use leptos_compare_image::{CompareImageGallery, ComparePair};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        view! {
+            <div style="max-width: 640px;">
+                <CompareImageGallery
+                    keep_position=true
+                    pairs=vec![
+                        ComparePair::new(img1_src, img2_src)
+                            .labels("Before", "After")
+                            .caption("First comparison"),
+                        ComparePair::new(img2_src, img1_src)
+                            .labels("After", "Before")
+                            .caption("Second comparison"),
+                    ]
+                />
+            </div>
+        }
+    })
+}
@@ -0,0 +1,71 @@
+use leptos::*;
+use leptos_compare_image::{
+    use_compare_image, ElementOverride, HandlePlacement, HandleVariant, LeptosCompareImage,
+    StyleOverrides,
+};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        view! {
+            <div style="max-width: 640px;">
+                <LeptosCompareImage
+                    handle_variant=HandleVariant::Pill
+                    left_image=img1_src
+                    right_image=img2_src
+                />
+                <LeptosCompareImage
+                    handle_placement=HandlePlacement::End
+                    handle_variant=HandleVariant::Chevrons
+                    left_image=img1_src
+                    right_image=img2_src
+                />
+                <LeptosCompareImage
+                    handle_placement=HandlePlacement::FollowPointer
+                    handle_variant=HandleVariant::Grip
+                    left_image=img1_src
+                    right_image=img2_src
+                />
+                <LeptosCompareImage
+                    handle_variant=HandleVariant::Line
+                    left_image=img1_src
+                    right_image=img2_src
+                    style_overrides=StyleOverrides {
+                        line: ElementOverride::new().css("box-shadow: 0 0 8px #e91e63"),
+                        ..StyleOverrides::default()
+                    }
+                />
+                <LeptosCompareImage
+                    left_arrow=Some(ViewFn::from(|| view! { <span>"◀"</span> }))
+                    left_image=img1_src
+                    right_arrow=Some(ViewFn::from(|| view! { <span>"▶"</span> }))
+                    right_image=img2_src
+                />
+                <LeptosCompareImage
+                    handle=Some(ViewFn::from(percentage))
+                    left_image=img1_src
+                    right_image=img2_src
+                    skeleton=Some(ViewFn::from(|| view! { <p>"Loading…"</p> }))
+                />
+            </div>
+        }
+    })
+}
+
+/// A handle showing how far the slider is, highlighted while dragging.
+fn percentage() -> impl IntoView {
+    let state = use_compare_image().expect("rendered inside `LeptosCompareImage`");
+
+    view! {
+        <span style=move || {
+            format!(
+                "padding: 4px 8px; border-radius: 4px; color: white; background: {};",
+                if state.is_sliding.get() { "#e91e63" } else { "rgba(0, 0, 0, 0.6)" },
+            )
+        }>{move || format!("{:.0}%", state.position.get() * 100.0)}</span>
+    }
+}
@@ -0,0 +1,39 @@
+use leptos::*;
+use leptos_compare_image::{LabelAutoHide, LabelPlacement, LeptosCompareImage};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        view! {
+            <div style="max-width: 640px;">
+                <LeptosCompareImage
+                    label_placement=LabelPlacement::StartCorners
+                    left_image=img1_src
+                    left_image_label=Some("Before".to_string())
+                    right_image=img2_src
+                    right_image_label=Some("After".to_string())
+                />
+                <LeptosCompareImage
+                    label_auto_hide=LabelAutoHide::Never
+                    label_placement=LabelPlacement::FollowDivider
+                    label_reveal_threshold=Some(0.2)
+                    left_image=img1_src
+                    left_image_label=Some("Before".to_string())
+                    right_image=img2_src
+                    right_image_label=Some("After".to_string())
+                />
+                <LeptosCompareImage
+                    label_auto_hide=LabelAutoHide::OnHover
+                    left_image=img1_src
+                    left_label=Some(ViewFn::from(|| view! { <strong>"v1.0"</strong> " (stable)" }))
+                    right_image=img2_src
+                    right_label=Some(ViewFn::from(|| view! { <strong>"v2.0"</strong> " (beta)" }))
+                />
+            </div>
+        }
+    })
+}
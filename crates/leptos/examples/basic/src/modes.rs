@@ -0,0 +1,56 @@
+use leptos::*;
+use leptos_compare_image::{LeptosCompareImage, Mode, Tint};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        let (blink_interval, set_blink_interval) = create_signal(Some(500.0));
+
+        view! {
+            <div style="max-width: 640px;">
+                "side by side"
+                <LeptosCompareImage
+                    left_image=img1_src
+                    left_image_label=Some("Before".to_string())
+                    mode=Mode::SideBySide
+                    pixel_inspector=true
+                    right_image=img2_src
+                    right_image_label=Some("After".to_string())
+                    zoomable=true
+                />
+                "blink, click or press space to toggle"
+                <LeptosCompareImage
+                    blink_interval=blink_interval
+                    left_image=img1_src
+                    left_image_label=Some("Before".to_string())
+                    mode=Mode::Blink
+                    right_image=img2_src
+                    right_image_label=Some("After".to_string())
+                />
+                <input
+                    type="range"
+                    min="0"
+                    max="2000"
+                    step="100"
+                    prop:value=move || blink_interval.get().unwrap_or_default()
+                    on:input=move |e| {
+                        let interval = event_target_value(&e).parse().unwrap_or_default();
+                        set_blink_interval.set((interval > 0.0).then_some(interval));
+                    }
+                />
+                "onion skin, drag the slider to fade between the images"
+                <LeptosCompareImage
+                    left_image=img1_src
+                    left_image_tint=Some(Tint::Red)
+                    mode=Mode::OnionSkin
+                    right_image=img2_src
+                    right_image_tint=Some(Tint::Cyan)
+                />
+            </div>
+        }
+    })
+}
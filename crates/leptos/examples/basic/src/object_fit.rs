@@ -0,0 +1,26 @@
+use leptos::*;
+use leptos_compare_image::{LeptosCompareImage, ObjectFit, ObjectPosition};
+
+pub fn main() {
+    _ = console_log::init_with_level(log::Level::Debug);
+    console_error_panic_hook::set_once();
+    let img1_src = "images/image1.png";
+    let img2_src = "images/image2.png";
+
+    mount_to_body(move || {
+        view! {
+            <div style="max-width: 640px;">
+                "contain"
+                <LeptosCompareImage
+                    left_image=img1_src
+                    left_image_object_fit=ObjectFit::Contain
+                    right_image=img2_src
+                    right_image_object_fit=ObjectFit::Contain
+                    right_image_object_position=ObjectPosition::TOP_LEFT
+                />
+                "native scale"
+                <LeptosCompareImage left_image=img1_src right_image=img2_src native_scale=true />
+            </div>
+        }
+    })
+}
@@ -1,5 +1,5 @@
 use leptos::*;
-use leptos_compare_image::LeptosCompareImage;
+use leptos_compare_image::{LeptosCompareImage, Orientation};
 
 pub fn main() {
     _ = console_log::init_with_level(log::Level::Debug);
@@ -13,7 +13,9 @@ pub fn main() {
                 "horizontal"
                 <LeptosCompareImage left_image=img1_src left_image_label=Some("Before".to_string()) right_image=img2_src right_image_label=Some("After".to_string()) />
                  "vertical"
-                <LeptosCompareImage left_image=img1_src left_image_label=Some("Before".to_string()) right_image=img2_src right_image_label=Some("After".to_string()) vertical=true />
+                <LeptosCompareImage left_image=img1_src left_image_label=Some("Before".to_string()) right_image=img2_src right_image_label=Some("After".to_string()) orientation=Some(Orientation::TopToBottom) />
+                 "bottom to top"
+                <LeptosCompareImage left_image=img1_src left_image_label=Some("Before".to_string()) right_image=img2_src right_image_label=Some("After".to_string()) orientation=Some(Orientation::BottomToTop) />
             </div>
         }
     })
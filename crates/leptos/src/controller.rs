@@ -0,0 +1,145 @@
+use std::rc::Rc;
+
+use js_sys::Function;
+use leptos::{
+    html::Div, store_value, Callable, Callback, NodeRef, ReadSignal, SignalGetUntracked,
+    StoredValue,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{Blob, Element, HtmlCanvasElement};
+
+use crate::{export::ExportOptions, fullscreen};
+
+type Export = Rc<dyn Fn(ExportOptions) -> Result<HtmlCanvasElement, JsValue>>;
+
+/// The parts of a mounted comparison a controller drives.
+#[derive(Clone)]
+pub(crate) struct Attached {
+    pub position: ReadSignal<f64>,
+    /// Moves the slider like the keyboard does, notifying and saving it.
+    pub set_position: Rc<dyn Fn(f64)>,
+    /// The element shown fullscreen.
+    pub frame_ref: NodeRef<Div>,
+    pub is_fullscreen: ReadSignal<bool>,
+    pub export: Export,
+}
+
+/// Drives a `LeptosCompareImage` from outside, pass it to the component's
+/// `controller` prop.
+#[derive(Clone, Copy)]
+pub struct CompareImageController {
+    attached: StoredValue<Option<Attached>>,
+}
+
+impl Default for CompareImageController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompareImageController {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            attached: store_value(None),
+        }
+    }
+
+    pub(crate) fn attach(&self, attached: Attached) {
+        self.attached.set_value(Some(attached));
+    }
+
+    pub(crate) fn detach(&self) {
+        self.attached.set_value(None);
+    }
+
+    /// The current slider position, `None` while no comparison is attached.
+    #[must_use]
+    pub fn position(&self) -> Option<f64> {
+        self.attached
+            .with_value(|attached| attached.as_ref().map(|a| a.position.get_untracked()))
+    }
+
+    /// Moves the slider to `position`, between `0` and `1`, reporting it
+    /// like a move made with the keyboard.
+    pub fn set_position(&self, position: f64) {
+        self.attached.with_value(|attached| {
+            if let Some(attached) = attached {
+                (attached.set_position)(position);
+            }
+        });
+    }
+
+    /// Whether the attached comparison is displayed fullscreen.
+    #[must_use]
+    pub fn is_fullscreen(&self) -> bool {
+        self.attached.with_value(|attached| {
+            attached
+                .as_ref()
+                .is_some_and(|attached| attached.is_fullscreen.get_untracked())
+        })
+    }
+
+    /// Displays the comparison fullscreen.
+    pub fn enter_fullscreen(&self) {
+        self.with_frame(fullscreen::enter);
+    }
+
+    /// Restores the inline layout.
+    pub fn exit_fullscreen(&self) {
+        self.with_frame(fullscreen::exit);
+    }
+
+    pub fn toggle_fullscreen(&self) {
+        self.with_frame(fullscreen::toggle);
+    }
+
+    /// Renders the current view of the comparison as a PNG data URL.
+    ///
+    /// # Errors
+    ///
+    /// Fails when no comparison is attached or the canvas cannot be read,
+    /// which happens when an image is cross-origin and loaded without CORS.
+    pub fn export_data_url(&self, options: ExportOptions) -> Result<String, JsValue> {
+        self.export(options)?.to_data_url_with_type("image/png")
+    }
+
+    /// Renders the current view of the comparison as a PNG `Blob`, passed to
+    /// `callback` once encoded. The callback receives `None` if encoding fails.
+    ///
+    /// # Errors
+    ///
+    /// Fails when no comparison is attached or the canvas cannot be read.
+    pub fn export_blob(
+        &self,
+        options: ExportOptions,
+        callback: Callback<Option<Blob>>,
+    ) -> Result<(), JsValue> {
+        let canvas = self.export(options)?;
+        let on_blob = Closure::once_into_js(move |blob: Option<Blob>| {
+            Callable::call(&callback, blob);
+        });
+
+        canvas.to_blob_with_type(on_blob.unchecked_ref::<Function>(), "image/png")
+    }
+
+    fn with_frame(&self, f: impl FnOnce(&Element)) {
+        self.attached.with_value(|attached| {
+            if let Some(frame) = attached
+                .as_ref()
+                .and_then(|attached| attached.frame_ref.get_untracked())
+            {
+                f(&frame);
+            }
+        });
+    }
+
+    fn export(&self, options: ExportOptions) -> Result<HtmlCanvasElement, JsValue> {
+        self.attached.with_value(|attached| {
+            let attached = attached
+                .as_ref()
+                .ok_or_else(|| JsValue::from_str("no comparison is attached to the controller"))?;
+            (attached.export)(options)
+        })
+    }
+}
@@ -0,0 +1,989 @@
+//! Typed CSS declarations the styles of the component are built from.
+
+use std::fmt::{self, Display, Formatter};
+
+use derive_more::derive::Display;
+
+macro_rules! properties {
+    ($($variant:ident => $name:literal,)*) => {
+        /// A CSS property set by the component.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Property {
+            $($variant,)*
+        }
+
+        impl Property {
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            /// The property called `name`, if the component sets it anywhere.
+            #[must_use]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+properties! {
+    AlignItems => "align-items",
+    Background => "background",
+    Border => "border",
+    BorderBottom => "border-bottom",
+    BorderLeft => "border-left",
+    BorderRadius => "border-radius",
+    BorderRight => "border-right",
+    BorderTop => "border-top",
+    Bottom => "bottom",
+    BoxShadow => "box-shadow",
+    BoxSizing => "box-sizing",
+    Clip => "clip",
+    ClipPath => "clip-path",
+    Color => "color",
+    Cursor => "cursor",
+    Display => "display",
+    Filter => "filter",
+    Flex => "flex",
+    FlexDirection => "flex-direction",
+    FontFamily => "font-family",
+    Gap => "gap",
+    Height => "height",
+    JustifyContent => "justify-content",
+    Left => "left",
+    Margin => "margin",
+    MarginLeft => "margin-left",
+    MarginRight => "margin-right",
+    MixBlendMode => "mix-blend-mode",
+    ObjectFit => "object-fit",
+    ObjectPosition => "object-position",
+    Opacity => "opacity",
+    Outline => "outline",
+    Overflow => "overflow",
+    OverflowX => "overflow-x",
+    Padding => "padding",
+    PointerEvents => "pointer-events",
+    Position => "position",
+    Right => "right",
+    Top => "top",
+    Transform => "transform",
+    TransformOrigin => "transform-origin",
+    Transition => "transition",
+    Visibility => "visibility",
+    WhiteSpace => "white-space",
+    Width => "width",
+}
+
+impl Display for Property {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Writes a number with at most two decimals and no trailing zeros.
+struct Number(f64);
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rounded = (self.0 * 100.0).round() / 100.0;
+        // also keeps `-0` out of the output
+        if rounded == 0.0 {
+            f.write_str("0")
+        } else {
+            write!(f, "{rounded}")
+        }
+    }
+}
+
+/// A length or a percentage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Auto,
+    Px(f64),
+    Percent(f64),
+    /// `calc()` of a percentage plus pixels.
+    Calc(f64, f64),
+}
+
+#[must_use]
+pub const fn px(value: f64) -> Length {
+    Length::Px(value)
+}
+
+#[must_use]
+pub const fn percent(value: f64) -> Length {
+    Length::Percent(value)
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Auto => f.write_str("auto"),
+            Self::Px(value) => write!(f, "{}px", Number(value)),
+            Self::Percent(value) => write!(f, "{}%", Number(value)),
+            Self::Calc(percent, px) => write!(
+                f,
+                "calc({}% {} {}px)",
+                Number(percent),
+                if px < 0.0 { '-' } else { '+' },
+                Number(px.abs())
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    Rgba(u8, u8, u8, f64),
+    /// `0xrrggbb`.
+    Hex(u32),
+    Named(&'static str),
+    /// Any other colour, such as one passed in by the user.
+    Custom(String),
+}
+
+impl Color {
+    pub const TRANSPARENT: Self = Self::Named("transparent");
+    pub const WHITE: Self = Self::Named("white");
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rgba(red, green, blue, alpha) => {
+                write!(f, "rgba({red}, {green}, {blue}, {})", Number(*alpha))
+            }
+            Self::Hex(hex) => write!(f, "#{hex:06x}"),
+            Self::Named(name) => f.write_str(name),
+            Self::Custom(color) => f.write_str(color),
+        }
+    }
+}
+
+/// A `transform`, applied as a translation, then a rotation, then a scale.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Transform {
+    translate: Option<(Length, Length)>,
+    rotate: Option<f64>,
+    scale: Option<f64>,
+}
+
+impl Transform {
+    pub const NONE: Self = Self {
+        translate: None,
+        rotate: None,
+        scale: None,
+    };
+
+    #[must_use]
+    pub const fn translate(x: Length, y: Length) -> Self {
+        Self {
+            translate: Some((x, y)),
+            ..Self::NONE
+        }
+    }
+
+    /// Adds a rotation by `degrees`.
+    #[must_use]
+    pub const fn rotate(self, degrees: f64) -> Self {
+        Self {
+            rotate: Some(degrees),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn scale(self, scale: f64) -> Self {
+        Self {
+            scale: Some(scale),
+            ..self
+        }
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if *self == Self::NONE {
+            return f.write_str("none");
+        }
+
+        let mut separator = "";
+        if let Some((x, y)) = self.translate {
+            write!(f, "translate({x}, {y})")?;
+            separator = " ";
+        }
+        if let Some(degrees) = self.rotate {
+            write!(f, "{separator}rotate({}deg)", Number(degrees))?;
+            separator = " ";
+        }
+        // clip rects are computed from the exact zoom, so rounding it would
+        // shift their edges
+        if let Some(scale) = self.scale {
+            write!(f, "{separator}scale({scale})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One function of a `filter`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    Grayscale(f64),
+    Sepia(f64),
+    Saturate(f64),
+    /// Rotation in degrees.
+    HueRotate(f64),
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Grayscale(amount) => write!(f, "grayscale({})", Number(amount)),
+            Self::Sepia(amount) => write!(f, "sepia({})", Number(amount)),
+            Self::Saturate(amount) => write!(f, "saturate({})", Number(amount)),
+            Self::HueRotate(degrees) => write!(f, "hue-rotate({}deg)", Number(degrees)),
+        }
+    }
+}
+
+/// The value of one declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A keyword, or any other value that never changes such as a shadow.
+    Keyword(&'static str),
+    Number(f64),
+    Length(Length),
+    /// Two lengths, such as vertical and horizontal padding.
+    Lengths(Length, Length),
+    Color(Color),
+    /// A `border` or `outline` of a width, a line style and a colour.
+    Stroke(Length, &'static str, Color),
+    Transform(Transform),
+    /// A `clip` rectangle, as its top, right, bottom and left edges.
+    Rect([Length; 4]),
+    /// A `clip-path` polygon.
+    Polygon(Vec<(Length, Length)>),
+    Filter(Vec<Filter>),
+    /// A value passed in by the user, written out as is.
+    Raw(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyword(keyword) => f.write_str(keyword),
+            Self::Number(number) => Number(*number).fmt(f),
+            Self::Length(length) => length.fmt(f),
+            Self::Lengths(first, second) => write!(f, "{first} {second}"),
+            Self::Color(color) => color.fmt(f),
+            Self::Stroke(width, style, color) => write!(f, "{width} {style} {color}"),
+            Self::Transform(transform) => transform.fmt(f),
+            Self::Rect([top, right, bottom, left]) => {
+                write!(f, "rect({top}, {right}, {bottom}, {left})")
+            }
+            Self::Polygon(points) => {
+                f.write_str("polygon(")?;
+                for (index, (x, y)) in points.iter().enumerate() {
+                    if index != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{x} {y}")?;
+                }
+                f.write_str(")")
+            }
+            Self::Filter(filters) => {
+                for (index, filter) in filters.iter().enumerate() {
+                    if index != 0 {
+                        f.write_str(" ")?;
+                    }
+                    filter.fmt(f)?;
+                }
+                Ok(())
+            }
+            Self::Raw(value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<&'static str> for Value {
+    fn from(keyword: &'static str) -> Self {
+        Self::Keyword(keyword)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(number: f64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<Length> for Value {
+    fn from(length: Length) -> Self {
+        Self::Length(length)
+    }
+}
+
+impl From<(Length, Length)> for Value {
+    fn from((first, second): (Length, Length)) -> Self {
+        Self::Lengths(first, second)
+    }
+}
+
+impl From<Color> for Value {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<Transform> for Value {
+    fn from(transform: Transform) -> Self {
+        Self::Transform(transform)
+    }
+}
+
+/// One declaration of user CSS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    /// Property name, lowercased unless it is a custom property.
+    pub name: String,
+    pub value: String,
+    pub important: bool,
+}
+
+impl Declaration {
+    /// The value, followed by `!important` when it is set.
+    fn priority_value(&self) -> Value {
+        Value::Raw(if self.important {
+            format!("{} !important", self.value)
+        } else {
+            self.value.clone()
+        })
+    }
+}
+
+impl Display for Declaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)?;
+        if self.important {
+            f.write_str(" !important")?;
+        }
+        Ok(())
+    }
+}
+
+/// A part of user CSS that could not be read, and was skipped.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+#[display("{kind} at byte {offset}")]
+pub struct CssError {
+    pub kind: CssErrorKind,
+    /// Where the skipped declaration or the unterminated token starts, in
+    /// bytes from the start of the CSS.
+    pub offset: usize,
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum CssErrorKind {
+    #[display("declaration without a `:`")]
+    MissingColon,
+    #[display("declaration without a property name")]
+    EmptyName,
+    #[display("invalid property name")]
+    InvalidName,
+    #[display("declaration without a value")]
+    EmptyValue,
+    #[display("unexpected closing bracket")]
+    UnexpectedBracket,
+    #[display("unclosed bracket")]
+    UnclosedBracket,
+    #[display("unterminated string")]
+    UnterminatedString,
+    #[display("unterminated comment")]
+    UnterminatedComment,
+}
+
+/// Declarations read from user CSS, along with whatever had to be skipped.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParsedCss {
+    pub declarations: Vec<Declaration>,
+    pub errors: Vec<CssError>,
+}
+
+/// Reads the declarations of an inline style such as `left_image_css`.
+/// Semicolons and colons inside strings, brackets and `url()`s are part of
+/// the value, comments are dropped, and every declaration that cannot be
+/// read is reported and skipped without affecting the others.
+#[must_use]
+pub fn parse_declarations(css: &str) -> ParsedCss {
+    let mut parsed = ParsedCss::default();
+    let mut chars = css.char_indices().peekable();
+    // the declaration being read, without its comments
+    let mut text = String::new();
+    // first character of the declaration
+    let mut start = None;
+    // where the name ends in `text`
+    let mut colon = None;
+    // open brackets and where they are
+    let mut brackets = Vec::new();
+    let mut error = None;
+
+    while let Some((offset, c)) = chars.next() {
+        if !c.is_whitespace() && c != ';' {
+            start.get_or_insert(offset);
+        }
+
+        match c {
+            '/' if chars.peek().is_some_and(|&(_, next)| next == '*') => {
+                chars.next();
+                let mut previous = None;
+                let closed = chars.any(|(_, c)| {
+                    let closes = previous == Some('*') && c == '/';
+                    previous = Some(c);
+                    closes
+                });
+                if !closed {
+                    parsed.errors.push(CssError {
+                        kind: CssErrorKind::UnterminatedComment,
+                        offset,
+                    });
+                    return parsed;
+                }
+                text.push(' ');
+            }
+            '"' | '\'' => {
+                text.push(c);
+                let mut closed = false;
+                while let Some((_, next)) = chars.next() {
+                    text.push(next);
+                    if next == '\\' {
+                        if let Some((_, escaped)) = chars.next() {
+                            text.push(escaped);
+                        }
+                    } else if next == c {
+                        closed = true;
+                        break;
+                    }
+                }
+                if !closed {
+                    parsed.errors.push(CssError {
+                        kind: CssErrorKind::UnterminatedString,
+                        offset,
+                    });
+                    return parsed;
+                }
+            }
+            '(' | '[' => {
+                brackets.push((c, offset));
+                text.push(c);
+            }
+            ')' | ']' => {
+                let open = if c == ')' { '(' } else { '[' };
+                if brackets.last().is_some_and(|&(last, _)| last == open) {
+                    brackets.pop();
+                } else {
+                    error.get_or_insert(CssError {
+                        kind: CssErrorKind::UnexpectedBracket,
+                        offset,
+                    });
+                }
+                text.push(c);
+            }
+            ':' if brackets.is_empty() && colon.is_none() => {
+                colon = Some(text.len());
+                text.push(c);
+            }
+            ';' if brackets.is_empty() => {
+                read_declaration(
+                    &mut parsed,
+                    &text,
+                    colon,
+                    start.take().unwrap_or(offset),
+                    error.take(),
+                );
+                text.clear();
+                colon = None;
+            }
+            _ => text.push(c),
+        }
+    }
+
+    // an unclosed bracket swallows everything after it
+    if let Some(&(_, offset)) = brackets.first() {
+        parsed.errors.push(CssError {
+            kind: CssErrorKind::UnclosedBracket,
+            offset,
+        });
+    } else {
+        read_declaration(&mut parsed, &text, colon, start.unwrap_or(css.len()), error);
+    }
+
+    parsed
+}
+
+/// Adds the declaration in `text`, whose name ends at `colon`, or the reason
+/// it cannot be read.
+fn read_declaration(
+    parsed: &mut ParsedCss,
+    text: &str,
+    colon: Option<usize>,
+    offset: usize,
+    error: Option<CssError>,
+) {
+    if text.trim().is_empty() {
+        return;
+    }
+
+    let kind = match (error, colon) {
+        (Some(error), _) => {
+            parsed.errors.push(error);
+            return;
+        }
+        (None, None) => CssErrorKind::MissingColon,
+        (None, Some(colon)) => {
+            let name = text[..colon].trim();
+            let (value, important) = split_important(text[colon + 1..].trim());
+
+            if name.is_empty() {
+                CssErrorKind::EmptyName
+            } else if !is_property_name(name) {
+                CssErrorKind::InvalidName
+            } else if value.is_empty() {
+                CssErrorKind::EmptyValue
+            } else {
+                parsed.declarations.push(Declaration {
+                    // custom properties are case-sensitive, the others are not
+                    name: if name.starts_with("--") {
+                        name.to_string()
+                    } else {
+                        name.to_ascii_lowercase()
+                    },
+                    value: value.to_string(),
+                    important,
+                });
+                return;
+            }
+        }
+    };
+
+    parsed.errors.push(CssError { kind, offset });
+}
+
+fn is_property_name(name: &str) -> bool {
+    !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii())
+}
+
+/// Splits a trailing `!important` off `value`.
+fn split_important(value: &str) -> (&str, bool) {
+    match value.rfind('!') {
+        Some(index) if value[index + 1..].trim().eq_ignore_ascii_case("important") => {
+            (value[..index].trim_end(), true)
+        }
+        _ => (value, false),
+    }
+}
+
+/// How user CSS is combined with the declarations of the component.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CssMerge {
+    /// User declarations replace those of the component.
+    UserOverrides,
+    /// User declarations only add properties the component does not set,
+    /// unless they are `!important`.
+    #[default]
+    ComponentWins,
+    /// User declarations are written after those of the component, leaving
+    /// the browser to pick between them.
+    Append,
+}
+
+/// Declarations of one element, written out in the order they were first set.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Style {
+    declarations: Vec<(Property, Value)>,
+    /// User declarations written after those of the component.
+    appended: Vec<Declaration>,
+}
+
+impl Style {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            declarations: Vec::new(),
+            appended: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with(mut self, property: Property, value: impl Into<Value>) -> Self {
+        self.set(property, value);
+        self
+    }
+
+    /// Sets `property`, replacing its previous value.
+    pub fn set(&mut self, property: Property, value: impl Into<Value>) {
+        let value = value.into();
+        match self
+            .declarations
+            .iter_mut()
+            .find(|(existing, _)| *existing == property)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.declarations.push((property, value)),
+        }
+    }
+
+    pub fn remove(&mut self, property: Property) {
+        self.declarations
+            .retain(|(existing, _)| *existing != property);
+    }
+
+    #[must_use]
+    pub fn get(&self, property: Property) -> Option<&Value> {
+        self.declarations
+            .iter()
+            .find(|(existing, _)| *existing == property)
+            .map(|(_, value)| value)
+    }
+
+    /// Combines user `declarations` with those already set.
+    pub fn merge(&mut self, declarations: &[Declaration], strategy: CssMerge) {
+        // set before the merge rather than by an earlier user declaration
+        let component = self.declarations.len();
+
+        for declaration in declarations {
+            match Property::from_name(&declaration.name) {
+                _ if strategy == CssMerge::Append => self.appended.push(declaration.clone()),
+                Some(property)
+                    if strategy == CssMerge::ComponentWins
+                        && !declaration.important
+                        && self.declarations[..component]
+                            .iter()
+                            .any(|(existing, _)| *existing == property) => {}
+                Some(property) => self.set(property, declaration.priority_value()),
+                None => self.append(declaration),
+            }
+        }
+    }
+
+    /// Sets every declaration of `other`, replacing those of the same
+    /// property.
+    pub fn extend(&mut self, other: &Self) {
+        for (property, value) in &other.declarations {
+            self.set(*property, value.clone());
+        }
+        for declaration in &other.appended {
+            self.append(declaration);
+        }
+    }
+
+    /// Writes a declaration the component does not know after the others,
+    /// replacing an earlier one of the same name.
+    fn append(&mut self, declaration: &Declaration) {
+        match self
+            .appended
+            .iter_mut()
+            .find(|existing| existing.name == declaration.name)
+        {
+            Some(existing) => existing.clone_from(declaration),
+            None => self.appended.push(declaration.clone()),
+        }
+    }
+
+    #[must_use]
+    pub fn as_css(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for (property, value) in &self.declarations {
+            write!(f, "{separator}{property}: {value};")?;
+            separator = " ";
+        }
+        for declaration in &self.appended {
+            write!(f, "{separator}{declaration};")?;
+            separator = " ";
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declaration(name: &str, value: &str, important: bool) -> Declaration {
+        Declaration {
+            name: name.to_string(),
+            value: value.to_string(),
+            important,
+        }
+    }
+
+    fn error(kind: CssErrorKind, offset: usize) -> CssError {
+        CssError { kind, offset }
+    }
+
+    #[test]
+    fn keeps_semicolons_inside_urls() {
+        let parsed = parse_declarations("background: url(data:image/png;base64,AAAA); color: red");
+
+        assert_eq!(
+            parsed.declarations,
+            [
+                declaration("background", "url(data:image/png;base64,AAAA)", false),
+                declaration("color", "red", false),
+            ]
+        );
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn keeps_nested_functions() {
+        let parsed =
+            parse_declarations("filter: drop-shadow(0 0 2px rgba(0, 0, 0, 0.5)) blur(1px)");
+
+        assert_eq!(
+            parsed.declarations,
+            [declaration(
+                "filter",
+                "drop-shadow(0 0 2px rgba(0, 0, 0, 0.5)) blur(1px)",
+                false
+            )]
+        );
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn drops_comments() {
+        let parsed = parse_declarations("/* lead */ color: /* inline */ red; /* trailing */");
+
+        assert_eq!(parsed.declarations, [declaration("color", "red", false)]);
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn reports_unterminated_comments() {
+        let parsed = parse_declarations("color: red; width: 10px /* oops");
+
+        assert_eq!(parsed.declarations, [declaration("color", "red", false)]);
+        assert_eq!(
+            parsed.errors,
+            [error(CssErrorKind::UnterminatedComment, 24)]
+        );
+    }
+
+    #[test]
+    fn keeps_quoted_strings() {
+        let parsed = parse_declarations(r#"content: "a;b\"c"; font-family: 'x:y'"#);
+
+        assert_eq!(
+            parsed.declarations,
+            [
+                declaration("content", r#""a;b\"c""#, false),
+                declaration("font-family", "'x:y'", false),
+            ]
+        );
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn reports_unterminated_strings() {
+        let parsed = parse_declarations(r#"content: "abc\""#);
+
+        assert!(parsed.declarations.is_empty());
+        assert_eq!(parsed.errors, [error(CssErrorKind::UnterminatedString, 9)]);
+    }
+
+    #[test]
+    fn reads_important() {
+        let parsed =
+            parse_declarations("color: red !important; width: 1px ! IMPORTANT; height: 2px");
+
+        assert_eq!(
+            parsed.declarations,
+            [
+                declaration("color", "red", true),
+                declaration("width", "1px", true),
+                declaration("height", "2px", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn lowercases_all_but_custom_properties() {
+        let parsed = parse_declarations("--My-Var: 1; COLOR: Red");
+
+        assert_eq!(
+            parsed.declarations,
+            [
+                declaration("--My-Var", "1", false),
+                declaration("color", "Red", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_declarations_and_keeps_the_others() {
+        for (css, kind, offset) in [
+            ("color red; width: 1px", CssErrorKind::MissingColon, 0),
+            ("width: 1px; : red", CssErrorKind::EmptyName, 12),
+            ("width: 1px;  1a: b", CssErrorKind::InvalidName, 13),
+            ("width: 1px; co lor: red", CssErrorKind::InvalidName, 12),
+            ("color: !important; width: 1px", CssErrorKind::EmptyValue, 0),
+            ("color: ; width: 1px", CssErrorKind::EmptyValue, 0),
+            (
+                "color: red); width: 1px",
+                CssErrorKind::UnexpectedBracket,
+                10,
+            ),
+            (
+                "width: 1px; color: red]",
+                CssErrorKind::UnexpectedBracket,
+                22,
+            ),
+        ] {
+            let parsed = parse_declarations(css);
+
+            assert_eq!(
+                parsed.declarations,
+                [declaration("width", "1px", false)],
+                "{css}"
+            );
+            assert_eq!(parsed.errors, [error(kind, offset)], "{css}");
+        }
+    }
+
+    #[test]
+    fn reports_unclosed_brackets() {
+        let parsed = parse_declarations("color: red; width: calc(1px");
+
+        assert_eq!(parsed.declarations, [declaration("color", "red", false)]);
+        assert_eq!(parsed.errors, [error(CssErrorKind::UnclosedBracket, 23)]);
+    }
+
+    #[test]
+    fn ignores_empty_declarations() {
+        let parsed = parse_declarations(" ;; color: red;; ");
+
+        assert_eq!(parsed.declarations, [declaration("color", "red", false)]);
+        assert!(parsed.errors.is_empty());
+    }
+
+    #[test]
+    fn writes_numbers_with_two_decimals() {
+        assert_eq!(px(1.0).to_string(), "1px");
+        assert_eq!(px(1.2345).to_string(), "1.23px");
+        assert_eq!(percent(-0.001).to_string(), "0%");
+        assert_eq!(px(-0.0).to_string(), "0px");
+    }
+
+    #[test]
+    fn writes_the_sign_of_calc() {
+        assert_eq!(Length::Calc(50.0, 10.0).to_string(), "calc(50% + 10px)");
+        assert_eq!(Length::Calc(50.0, -10.0).to_string(), "calc(50% - 10px)");
+        assert_eq!(Length::Calc(50.0, -0.0).to_string(), "calc(50% + 0px)");
+    }
+
+    #[test]
+    fn writes_transforms() {
+        assert_eq!(Transform::NONE.to_string(), "none");
+        assert_eq!(
+            Transform::translate(px(-10.0), percent(50.0))
+                .rotate(45.0)
+                .scale(1.125)
+                .to_string(),
+            "translate(-10px, 50%) rotate(45deg) scale(1.125)"
+        );
+        assert_eq!(Transform::NONE.scale(2.0).to_string(), "scale(2)");
+    }
+
+    #[test]
+    fn writes_polygons() {
+        assert_eq!(
+            Value::Polygon(vec![
+                (px(0.0), px(0.0)),
+                (percent(100.0), px(0.0)),
+                (Length::Calc(50.0, -4.0), percent(100.0)),
+            ])
+            .to_string(),
+            "polygon(0px 0px, 100% 0px, calc(50% - 4px) 100%)"
+        );
+    }
+
+    fn merged(css: &str, strategy: CssMerge) -> String {
+        let mut style = Style::new().with(Property::Opacity, 1.0);
+        style.merge(&parse_declarations(css).declarations, strategy);
+        style.as_css()
+    }
+
+    #[test]
+    fn user_declarations_override() {
+        assert_eq!(
+            merged("opacity: 0.5; --x: 1", CssMerge::UserOverrides),
+            "opacity: 0.5; --x: 1;"
+        );
+    }
+
+    #[test]
+    fn component_declarations_win() {
+        assert_eq!(
+            merged(
+                "opacity: 0.5; width: 1px; width: 2px; --x: 1",
+                CssMerge::ComponentWins
+            ),
+            "opacity: 1; width: 2px; --x: 1;"
+        );
+        assert_eq!(
+            merged("opacity: 0.5 !important", CssMerge::ComponentWins),
+            "opacity: 0.5 !important;"
+        );
+    }
+
+    #[test]
+    fn user_declarations_are_appended() {
+        assert_eq!(
+            merged("opacity: 0.5; --x: 1", CssMerge::Append),
+            "opacity: 1; opacity: 0.5; --x: 1;"
+        );
+    }
+
+    #[test]
+    fn custom_properties_replace_each_other() {
+        assert_eq!(
+            merged("--x: 1; --x: 2", CssMerge::ComponentWins),
+            "opacity: 1; --x: 2;"
+        );
+        assert_eq!(
+            merged("--x: 1; --x: 2", CssMerge::Append),
+            "opacity: 1; --x: 1; --x: 2;"
+        );
+    }
+
+    #[test]
+    fn extending_matches_overriding() {
+        let mut overrides = Style::new();
+        overrides.merge(
+            &parse_declarations("opacity: 0.5; --x: 1").declarations,
+            CssMerge::UserOverrides,
+        );
+
+        let mut style = Style::new()
+            .with(Property::Opacity, 1.0)
+            .with(Property::Width, px(2.0));
+        style.extend(&overrides);
+        assert_eq!(style.as_css(), "opacity: 0.5; width: 2px; --x: 1;");
+    }
+}
@@ -0,0 +1,320 @@
+use std::{io::Cursor, path::Path};
+
+use image::{DynamicImage, GrayImage, ImageFormat, ImageResult, Luma, Rgba, RgbaImage};
+
+use crate::Region;
+
+/// Controls how two images are compared by [`diff_images`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Largest per-channel difference that is still treated as unchanged.
+    pub threshold: u8,
+    /// Changed regions closer than this many pixels are merged into one.
+    pub merge_distance: u32,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 16,
+            merge_distance: 4,
+        }
+    }
+}
+
+/// The result of comparing two images pixel by pixel.
+///
+/// Both images are aligned at their top-left corner and compared over the
+/// union of their sizes, pixels covered by only one image count as changed.
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    /// Largest per-channel difference of every pixel.
+    pub difference: GrayImage,
+    /// `255` for pixels whose difference exceeds the threshold, `0` otherwise.
+    pub mask: GrayImage,
+    /// Bounding boxes of the changed areas.
+    pub regions: Vec<Region>,
+}
+
+impl ImageDiff {
+    /// Number of pixels marked as changed in the mask.
+    #[must_use]
+    pub fn changed_pixels(&self) -> usize {
+        self.mask
+            .pixels()
+            .filter(|Luma([value])| *value > 0)
+            .count()
+    }
+
+    /// Paints the changed pixels with `color` on a transparent image, to be
+    /// layered above the right image with `overlay_image`.
+    #[must_use]
+    pub fn overlay(&self, color: Rgba<u8>) -> RgbaImage {
+        RgbaImage::from_fn(self.mask.width(), self.mask.height(), |x, y| {
+            if self.mask.get_pixel(x, y)[0] > 0 {
+                color
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        })
+    }
+
+    /// Encodes [`ImageDiff::overlay`] as PNG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image could not be encoded.
+    pub fn overlay_png(&self, color: Rgba<u8>) -> ImageResult<Vec<u8>> {
+        let mut buf = Cursor::new(Vec::new());
+        self.overlay(color).write_to(&mut buf, ImageFormat::Png)?;
+        Ok(buf.into_inner())
+    }
+}
+
+/// Loads two images from disk and compares them.
+///
+/// # Errors
+///
+/// Returns an error if either image could not be read or decoded.
+pub fn diff_files(
+    left: impl AsRef<Path>,
+    right: impl AsRef<Path>,
+    options: &DiffOptions,
+) -> ImageResult<ImageDiff> {
+    let left = image::open(left)?;
+    let right = image::open(right)?;
+
+    Ok(diff_images(&left, &right, options))
+}
+
+/// Compares two decoded images.
+#[must_use]
+pub fn diff_images(left: &DynamicImage, right: &DynamicImage, options: &DiffOptions) -> ImageDiff {
+    let left = left.to_rgba8();
+    let right = right.to_rgba8();
+
+    let width = left.width().max(right.width());
+    let height = left.height().max(right.height());
+
+    let difference = GrayImage::from_fn(width, height, |x, y| {
+        match (left.get_pixel_checked(x, y), right.get_pixel_checked(x, y)) {
+            (Some(Rgba(left)), Some(Rgba(right))) => Luma([left
+                .iter()
+                .zip(right)
+                .map(|(left, right)| left.abs_diff(*right))
+                .max()
+                .unwrap_or_default()]),
+            _ => Luma([u8::MAX]),
+        }
+    });
+
+    let mask = GrayImage::from_fn(width, height, |x, y| {
+        if difference.get_pixel(x, y)[0] > options.threshold {
+            Luma([u8::MAX])
+        } else {
+            Luma([0])
+        }
+    });
+
+    let regions = merge_regions(changed_regions(&mask), options.merge_distance);
+
+    ImageDiff {
+        difference,
+        mask,
+        regions,
+    }
+}
+
+/// Bounding boxes of the 8-connected components of the mask.
+fn changed_regions(mask: &GrayImage) -> Vec<Region> {
+    let (width, height) = mask.dimensions();
+    let mut visited = vec![false; width as usize * height as usize];
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+
+    for (x, y, pixel) in mask.enumerate_pixels() {
+        let index = (y * width + x) as usize;
+        if pixel[0] == 0 || visited[index] {
+            continue;
+        }
+
+        visited[index] = true;
+        stack.push((x, y));
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (x, y, x, y);
+
+        while let Some((x, y)) = stack.pop() {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    let index = (ny * width + nx) as usize;
+                    if !visited[index] && mask.get_pixel(nx, ny)[0] > 0 {
+                        visited[index] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+        }
+
+        regions.push(Region {
+            x: min_x,
+            y: min_y,
+            width: max_x - min_x + 1,
+            height: max_y - min_y + 1,
+        });
+    }
+
+    regions
+}
+
+/// Merges regions that overlap once grown by `distance` pixels.
+fn merge_regions(mut regions: Vec<Region>, distance: u32) -> Vec<Region> {
+    let mut merged = true;
+
+    while merged {
+        merged = false;
+        let mut index = 0;
+
+        while index < regions.len() {
+            let mut other = index + 1;
+
+            while other < regions.len() {
+                if regions[index].expand(distance).intersects(&regions[other]) {
+                    let region = regions.swap_remove(other);
+                    regions[index] = regions[index].union(&region);
+                    merged = true;
+                } else {
+                    other += 1;
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A black image with the given pixels set to a grey `value`.
+    fn image(width: u32, height: u32, pixels: &[(u32, u32, u8)]) -> DynamicImage {
+        let mut image = RgbaImage::from_pixel(width, height, Rgba([0, 0, 0, 255]));
+        for &(x, y, value) in pixels {
+            image.put_pixel(x, y, Rgba([value, value, value, 255]));
+        }
+        DynamicImage::ImageRgba8(image)
+    }
+
+    fn options(threshold: u8, merge_distance: u32) -> DiffOptions {
+        DiffOptions {
+            threshold,
+            merge_distance,
+        }
+    }
+
+    #[test]
+    fn identical_images_have_no_regions() {
+        let left = image(8, 8, &[(3, 3, 200)]);
+        let diff = diff_images(&left, &left, &DiffOptions::default());
+
+        assert_eq!(diff.changed_pixels(), 0);
+        assert!(diff.regions.is_empty());
+    }
+
+    #[test]
+    fn differences_up_to_the_threshold_are_unchanged() {
+        let left = image(4, 4, &[]);
+        let right = image(4, 4, &[(0, 0, 16), (2, 2, 17)]);
+        let diff = diff_images(&left, &right, &options(16, 0));
+
+        assert_eq!(diff.difference.get_pixel(0, 0)[0], 16);
+        assert_eq!(diff.changed_pixels(), 1);
+        assert_eq!(
+            diff.regions,
+            [Region {
+                x: 2,
+                y: 2,
+                width: 1,
+                height: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn diagonal_pixels_are_connected() {
+        let mask = GrayImage::from_fn(4, 4, |x, y| Luma([if x == y { 255 } else { 0 }]));
+
+        assert_eq!(
+            changed_regions(&mask),
+            [Region {
+                x: 0,
+                y: 0,
+                width: 4,
+                height: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn merges_regions_closer_than_the_distance() {
+        let left = image(8, 3, &[]);
+        // two unchanged pixels apart
+        let right = image(8, 3, &[(1, 1, 255), (4, 1, 255)]);
+
+        assert_eq!(diff_images(&left, &right, &options(16, 2)).regions.len(), 2);
+        assert_eq!(
+            diff_images(&left, &right, &options(16, 3)).regions,
+            [Region {
+                x: 1,
+                y: 1,
+                width: 4,
+                height: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn merging_repeats_until_no_regions_overlap() {
+        let regions = [0, 10, 5].map(|x| Region {
+            x,
+            y: 0,
+            width: 1,
+            height: 1,
+        });
+
+        // the first one only reaches the second once merged with the third
+        assert_eq!(
+            merge_regions(regions.to_vec(), 5),
+            [Region {
+                x: 0,
+                y: 0,
+                width: 11,
+                height: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn pixels_outside_one_image_are_changed() {
+        let left = image(2, 2, &[]);
+        let right = image(3, 2, &[]);
+        let diff = diff_images(&left, &right, &options(16, 0));
+
+        assert_eq!(diff.mask.dimensions(), (3, 2));
+        assert_eq!(
+            diff.regions,
+            [Region {
+                x: 2,
+                y: 0,
+                width: 1,
+                height: 2
+            }]
+        );
+    }
+}
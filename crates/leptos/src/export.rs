@@ -0,0 +1,311 @@
+use leptos::document;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+use crate::{
+    layout::{label_anchors, Divider, LabelAnchor, Pane},
+    style, LabelPlacement, Mode, Orientation, Placement, Tint, Viewport,
+};
+
+const LABEL_FONT_SIZE: f64 = 16.0;
+
+/// Size of an exported image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExportResolution {
+    /// The size the comparison is displayed at.
+    #[default]
+    Display,
+    /// Scaled so the right image is drawn at its natural size.
+    Natural,
+}
+
+/// What an export of the comparison includes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub resolution: ExportResolution,
+    pub divider: bool,
+    /// Draws the labels given as text where they are shown. Labels given as
+    /// views cannot be drawn on a canvas and are left out.
+    pub labels: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            resolution: ExportResolution::Display,
+            divider: true,
+            labels: true,
+        }
+    }
+}
+
+/// Everything needed to draw the current state of a comparison.
+pub(crate) struct Snapshot<'a> {
+    pub left_image: &'a HtmlImageElement,
+    pub right_image: &'a HtmlImageElement,
+    pub left_placement: Placement,
+    pub right_placement: Placement,
+    pub left_label: Option<&'a str>,
+    pub right_label: Option<&'a str>,
+    pub label_placement: LabelPlacement,
+    /// Whether the label of the left and of the right image are shown.
+    pub labels_visible: (bool, bool),
+    pub container_width: f64,
+    pub container_height: f64,
+    pub slider_position: f64,
+    pub slider_line_color: &'a str,
+    pub slider_line_width: f64,
+    pub orientation: Orientation,
+    pub viewport: Viewport,
+    pub mode: Mode,
+    pub showing_left: bool,
+    pub left_opacity: f64,
+    pub left_tint: Option<Tint>,
+    pub right_tint: Option<Tint>,
+    pub angle: Option<f64>,
+}
+
+impl Snapshot<'_> {
+    /// Composites the comparison onto a new canvas.
+    pub(crate) fn draw(&self, options: ExportOptions) -> Result<HtmlCanvasElement, JsValue> {
+        let scale = match options.resolution {
+            ExportResolution::Display => 1.0,
+            ExportResolution::Natural => 1.0 / (self.right_placement.scale_x * self.viewport.zoom),
+        };
+
+        let canvas = document()
+            .create_element("canvas")?
+            .dyn_into::<HtmlCanvasElement>()?;
+        canvas.set_width((self.container_width * scale).round() as u32);
+        canvas.set_height((self.container_height * scale).round() as u32);
+
+        let context = canvas
+            .get_context("2d")?
+            .ok_or_else(|| JsValue::from_str("2d canvas context is not available"))?
+            .dyn_into::<CanvasRenderingContext2d>()?;
+        context.scale(scale, scale)?;
+
+        let horizontal = self.orientation.is_horizontal();
+        let reversed = self.orientation.is_reversed();
+        let (left_pane, right_pane) = Pane::split(
+            self.mode,
+            self.orientation,
+            self.container_width,
+            self.container_height,
+        );
+        let angled = self
+            .angle
+            .filter(|_| self.mode == Mode::Slider)
+            .map(|angle| {
+                (
+                    angle,
+                    Divider::new(angle, self.container_width, self.container_height),
+                )
+            });
+        let (left_clip, right_clip) = match (self.mode, angled) {
+            (Mode::Slider, Some((_, divider))) => (
+                divider.polygon(self.slider_position, true),
+                divider.polygon(self.slider_position, false),
+            ),
+            (Mode::Slider, None) => {
+                let divider = self.divider();
+                let left_clip = match (horizontal, reversed) {
+                    (true, false) => Pane {
+                        width: divider,
+                        ..left_pane
+                    },
+                    (true, true) => Pane {
+                        x: divider,
+                        width: left_pane.width - divider,
+                        ..left_pane
+                    },
+                    (false, false) => Pane {
+                        height: divider,
+                        ..left_pane
+                    },
+                    (false, true) => Pane {
+                        y: divider,
+                        height: left_pane.height - divider,
+                        ..left_pane
+                    },
+                };
+                (left_clip.corners(), right_pane.corners())
+            }
+            _ => (left_pane.corners(), right_pane.corners()),
+        };
+
+        draw_clipped(&context, &right_clip, || {
+            self.draw_image(
+                &context,
+                self.right_image,
+                self.right_placement,
+                self.right_tint,
+            )
+        })?;
+        if self.mode != Mode::Blink || self.showing_left {
+            draw_clipped(&context, &left_clip, || {
+                context.set_global_alpha(self.left_opacity);
+                self.draw_image(
+                    &context,
+                    self.left_image,
+                    self.left_placement,
+                    self.left_tint,
+                )
+            })?;
+        }
+
+        if options.divider && self.mode == Mode::Slider {
+            context.set_fill_style_str(self.slider_line_color);
+            if let Some((angle, divider)) = angled {
+                let (x, y) = divider.center(self.slider_position);
+                let length = self.container_width.hypot(self.container_height);
+                context.save();
+                context.translate(x, y)?;
+                context.rotate(angle.to_radians())?;
+                context.fill_rect(
+                    -self.slider_line_width / 2.0,
+                    -length / 2.0,
+                    self.slider_line_width,
+                    length,
+                );
+                context.restore();
+            } else if horizontal {
+                let divider = self.divider();
+                context.fill_rect(
+                    divider - self.slider_line_width / 2.0,
+                    0.0,
+                    self.slider_line_width,
+                    self.container_height,
+                );
+            } else {
+                let divider = self.divider();
+                context.fill_rect(
+                    0.0,
+                    divider - self.slider_line_width / 2.0,
+                    self.container_width,
+                    self.slider_line_width,
+                );
+            }
+        }
+
+        if options.labels {
+            // side by side each label stays within the half of its image
+            let split = if self.mode == Mode::SideBySide {
+                0.5
+            } else {
+                self.split()
+            };
+            let divider = self
+                .angle
+                .filter(|_| matches!(self.mode, Mode::Slider | Mode::OnionSkin))
+                .map(|angle| {
+                    (
+                        Divider::new(angle, self.container_width, self.container_height),
+                        self.slider_position,
+                    )
+                });
+            let (left_anchor, right_anchor) = label_anchors(
+                self.label_placement,
+                self.orientation,
+                (self.container_width, self.container_height),
+                split,
+                divider,
+            );
+            let (left_visible, right_visible) = self.labels_visible;
+
+            // each label is cut off where its image is, as it is on screen
+            for (label, anchor, visible, clip) in [
+                (self.left_label, left_anchor, left_visible, &left_clip),
+                (self.right_label, right_anchor, right_visible, &right_clip),
+            ] {
+                if let Some(label) = label.filter(|_| visible) {
+                    draw_clipped(&context, clip, || draw_label(&context, label, anchor))?;
+                }
+            }
+        }
+
+        Ok(canvas)
+    }
+
+    /// Distance of the split from the left or top edge, as a fraction.
+    fn split(&self) -> f64 {
+        if self.orientation.is_reversed() {
+            1.0 - self.slider_position
+        } else {
+            self.slider_position
+        }
+    }
+
+    /// Distance of the split from the left or top edge.
+    fn divider(&self) -> f64 {
+        if self.orientation.is_horizontal() {
+            self.container_width * self.split()
+        } else {
+            self.container_height * self.split()
+        }
+    }
+
+    fn draw_image(
+        &self,
+        context: &CanvasRenderingContext2d,
+        image: &HtmlImageElement,
+        placement: Placement,
+        tint: Option<Tint>,
+    ) -> Result<(), JsValue> {
+        if let Some(tint) = tint {
+            context.set_filter(&style::tint_filter(tint).to_string());
+        }
+
+        let (left, top) = placement.to_container(self.viewport, 0.0, 0.0);
+        let (right, bottom) = placement.to_container(
+            self.viewport,
+            f64::from(image.natural_width()),
+            f64::from(image.natural_height()),
+        );
+
+        context.draw_image_with_html_image_element_and_dw_and_dh(
+            image,
+            left,
+            top,
+            right - left,
+            bottom - top,
+        )
+    }
+}
+
+/// Runs `draw` with everything outside of `polygon` masked out.
+fn draw_clipped(
+    context: &CanvasRenderingContext2d,
+    polygon: &[(f64, f64)],
+    draw: impl FnOnce() -> Result<(), JsValue>,
+) -> Result<(), JsValue> {
+    context.save();
+    context.begin_path();
+    for &(x, y) in polygon {
+        context.line_to(x, y);
+    }
+    context.close_path();
+    context.clip();
+    let drawn = draw();
+    context.restore();
+    drawn
+}
+
+/// Draws a label box like the ones rendered by the component at `anchor`.
+fn draw_label(
+    context: &CanvasRenderingContext2d,
+    label: &str,
+    anchor: LabelAnchor,
+) -> Result<(), JsValue> {
+    context.set_font(&format!("{LABEL_FONT_SIZE}px sans-serif"));
+    context.set_text_baseline("middle");
+
+    let width = context.measure_text(label)?.width() + 40.0;
+    let height = LABEL_FONT_SIZE + 20.0;
+    let (left, top) = anchor.origin(width, height);
+
+    context.set_fill_style_str("rgba(0, 0, 0, 0.5)");
+    context.fill_rect(left, top, width, height);
+    context.set_fill_style_str("white");
+    context.fill_text(label, left + 20.0, top + height / 2.0)
+}
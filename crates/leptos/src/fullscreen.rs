@@ -0,0 +1,29 @@
+use leptos::{document, logging::warn};
+use web_sys::Element;
+
+/// Whether `element` is the current fullscreen element.
+pub(crate) fn is_active(element: &Element) -> bool {
+    document()
+        .fullscreen_element()
+        .is_some_and(|fullscreen| &fullscreen == element)
+}
+
+pub(crate) fn enter(element: &Element) {
+    if let Err(err) = element.request_fullscreen() {
+        warn!("failed to enter fullscreen: {err:?}");
+    }
+}
+
+pub(crate) fn exit(element: &Element) {
+    if is_active(element) {
+        document().exit_fullscreen();
+    }
+}
+
+pub(crate) fn toggle(element: &Element) {
+    if is_active(element) {
+        exit(element);
+    } else {
+        enter(element);
+    }
+}
@@ -0,0 +1,184 @@
+use leptos::{
+    component, create_rw_signal, ev::KeyboardEvent, view, Callable, Callback, CollectView,
+    IntoView, MaybeSignal, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalWith,
+};
+
+use crate::{style, LeptosCompareImage};
+
+/// One before/after pair shown by [`CompareImageGallery`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ComparePair {
+    pub left_image: String,
+    pub right_image: String,
+    pub left_image_label: Option<String>,
+    pub right_image_label: Option<String>,
+    pub caption: Option<String>,
+}
+
+impl ComparePair {
+    #[must_use]
+    pub fn new(left_image: impl Into<String>, right_image: impl Into<String>) -> Self {
+        Self {
+            left_image: left_image.into(),
+            right_image: right_image.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn labels(mut self, left: impl Into<String>, right: impl Into<String>) -> Self {
+        self.left_image_label = Some(left.into());
+        self.right_image_label = Some(right.into());
+        self
+    }
+
+    #[must_use]
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+}
+
+/// Presents a sequence of comparisons with previous/next navigation,
+/// thumbnails and keyboard shortcuts (arrow keys or page up/down when the
+/// slider does not handle them).
+#[component]
+#[must_use]
+pub fn CompareImageGallery(
+    #[prop(default = 0)] initial_index: usize,
+    #[prop(default = false)] keep_position: bool,
+    #[prop(default = None)] on_change: Option<Callback<usize>>,
+    #[prop(into)] pairs: MaybeSignal<Vec<ComparePair>>,
+    #[prop(default = true)] preload: bool,
+    #[prop(default = true)] thumbnails: bool,
+) -> impl IntoView {
+    let pairs = Signal::derive(move || pairs.get());
+    let index = create_rw_signal(initial_index);
+    let position = create_rw_signal(0.5);
+
+    let len = move || pairs.with(Vec::len);
+    let go_to = move |next: usize| {
+        if len() == 0 || next == index.get_untracked() {
+            return;
+        }
+
+        index.set(next);
+        if let Some(on_change) = on_change {
+            Callable::call(&on_change, next);
+        }
+    };
+    let step = move |forward: bool| {
+        let len = len();
+        if len > 0 {
+            let current = index.get_untracked() % len;
+            go_to(if forward {
+                (current + 1) % len
+            } else {
+                (current + len - 1) % len
+            });
+        }
+    };
+
+    let handle_keydown = move |e: KeyboardEvent| {
+        // the slider claims the arrow keys while it has focus
+        if e.default_prevented() {
+            return;
+        }
+
+        match e.key().as_str() {
+            "ArrowLeft" | "PageUp" => step(false),
+            "ArrowRight" | "PageDown" => step(true),
+            _ => return,
+        }
+        e.prevent_default();
+    };
+
+    view! {
+        <div data-testid="gallery" tabindex="0" on:keydown=handle_keydown>
+            {move || {
+                let current = index.get();
+                pairs
+                    .with(|pairs| pairs.get(current).cloned())
+                    .map(|pair| {
+                        view! {
+                            <LeptosCompareImage
+                                caption=pair.caption
+                                left_image=pair.left_image
+                                left_image_label=pair.left_image_label
+                                right_image=pair.right_image
+                                right_image_label=pair.right_image_label
+                                slider_position_percentage=if keep_position {
+                                    position.get_untracked()
+                                } else {
+                                    0.5
+                                }
+                                on_slider_position_change=Some(
+                                    Callback::new(move |value| position.set(value)),
+                                )
+                            />
+                        }
+                    })
+            }}
+            <div style=style::gallery_nav().as_css()>
+                <button type="button" aria-label="Previous comparison" on:click=move |_| step(false)>
+                    "‹"
+                </button>
+                <span>{move || format!("{} / {}", (index.get() + 1).min(len()), len())}</span>
+                <button type="button" aria-label="Next comparison" on:click=move |_| step(true)>
+                    "›"
+                </button>
+            </div>
+            {move || {
+                thumbnails
+                    .then(|| {
+                        view! {
+                            <div style=style::gallery_thumbnails().as_css()>
+                                {pairs
+                                    .get()
+                                    .into_iter()
+                                    .enumerate()
+                                    .map(|(i, pair)| {
+                                        view! {
+                                            <button
+                                                type="button"
+                                                aria-label=pair
+                                                    .caption
+                                                    .clone()
+                                                    .unwrap_or_else(|| format!("Comparison {}", i + 1))
+                                                aria-current=move || (index.get() == i).then_some("true")
+                                                style=move || {
+                                                    style::gallery_thumbnail(index.get() == i).as_css()
+                                                }
+                                                on:click=move |_| go_to(i)
+                                            >
+                                                <img
+                                                    alt=""
+                                                    src=pair.right_image
+                                                    style=style::gallery_thumbnail_image().as_css()
+                                                />
+                                            </button>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+            }}
+            {move || {
+                let len = len();
+                (preload && len > 1)
+                    .then(|| {
+                        let next = (index.get() + 1) % len;
+                        pairs
+                            .with(|pairs| pairs.get(next).cloned())
+                            .map(|pair| {
+                                view! {
+                                    <link rel="preload" attr:as="image" href=pair.left_image />
+                                    <link rel="preload" attr:as="image" href=pair.right_image />
+                                }
+                            })
+                    })
+            }}
+        </div>
+    }
+}
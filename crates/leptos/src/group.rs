@@ -0,0 +1,39 @@
+use leptos::{
+    component, create_rw_signal, provide_context, use_context, Children, IntoView, RwSignal,
+};
+
+use crate::Viewport;
+
+/// Shared state of a [`CompareImageGroup`], picked up by every
+/// `LeptosCompareImage` rendered inside it.
+#[derive(Debug, Clone, Copy)]
+pub struct CompareImageGroupContext {
+    pub position: RwSignal<f64>,
+    pub viewport: Option<RwSignal<Viewport>>,
+}
+
+/// Returns the context of the enclosing [`CompareImageGroup`], if any.
+#[must_use]
+pub fn use_compare_image_group() -> Option<CompareImageGroupContext> {
+    use_context::<CompareImageGroupContext>()
+}
+
+/// Keeps the slider position, and optionally the zoom and pan, of every
+/// comparison rendered inside it in sync.
+///
+/// Members start at the group's `slider_position_percentage`, their own
+/// `slider_position_percentage` is ignored. A comparison with
+/// `sync_with_group` unset keeps its own position and viewport.
+#[component]
+pub fn CompareImageGroup(
+    children: Children,
+    #[prop(default = 0.5, into)] slider_position_percentage: f64,
+    #[prop(default = false)] sync_viewport: bool,
+) -> impl IntoView {
+    provide_context(CompareImageGroupContext {
+        position: create_rw_signal(slider_position_percentage),
+        viewport: sync_viewport.then(|| create_rw_signal(Viewport::default())),
+    });
+
+    children()
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use leptos::document;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+/// Colour of a single pixel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// The colour as `#rrggbbaa`.
+    #[must_use]
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+    }
+}
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, self.a)
+    }
+}
+
+/// The pixels of both images under the pointer.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PixelSample {
+    /// Pointer position in container pixels.
+    pub x: f64,
+    pub y: f64,
+    /// `None` when the pointer is outside the image or the image could not be
+    /// read, for instance because it was loaded without CORS.
+    pub left: Option<Rgba>,
+    pub right: Option<Rgba>,
+}
+
+impl PixelSample {
+    /// Per-channel difference `right - left`, when both pixels are known.
+    #[must_use]
+    pub fn delta(&self) -> Option<[i16; 4]> {
+        let (left, right) = self.left.zip(self.right)?;
+
+        Some([
+            i16::from(right.r) - i16::from(left.r),
+            i16::from(right.g) - i16::from(left.g),
+            i16::from(right.b) - i16::from(left.b),
+            i16::from(right.a) - i16::from(left.a),
+        ])
+    }
+}
+
+/// A 1×1 canvas pixels are read through, kept for the lifetime of a
+/// component instead of being created for every sample.
+pub(crate) struct Sampler {
+    context: CanvasRenderingContext2d,
+    /// Sources that cannot be read because they were loaded without CORS.
+    unreadable: Vec<String>,
+}
+
+impl Sampler {
+    pub(crate) fn new() -> Option<Self> {
+        Some(Self {
+            context: context()?,
+            unreadable: Vec::new(),
+        })
+    }
+
+    /// Reads the pixel at natural coordinates (`x`, `y`) of `image`.
+    pub(crate) fn sample(&mut self, image: &HtmlImageElement, x: f64, y: f64) -> Option<Rgba> {
+        let (x, y) = (x.floor(), y.floor());
+        if x < 0.0
+            || y < 0.0
+            || x >= f64::from(image.natural_width())
+            || y >= f64::from(image.natural_height())
+        {
+            return None;
+        }
+
+        let source = image.current_src();
+        if self.unreadable.contains(&source) {
+            return None;
+        }
+
+        // a translucent pixel would otherwise blend with the previous one
+        self.context.clear_rect(0.0, 0.0, 1.0, 1.0);
+        self.context
+            .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+                image, x, y, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0,
+            )
+            .ok()?;
+
+        let Ok(data) = self.context.get_image_data(0.0, 0.0, 1.0, 1.0) else {
+            // a cross-origin image taints the canvas for good, a new one is
+            // needed to keep reading the other images
+            self.unreadable.push(source);
+            self.context = context()?;
+            return None;
+        };
+        let data = data.data();
+
+        Some(Rgba {
+            r: data[0],
+            g: data[1],
+            b: data[2],
+            a: data[3],
+        })
+    }
+}
+
+fn context() -> Option<CanvasRenderingContext2d> {
+    let canvas = document()
+        .create_element("canvas")
+        .ok()?
+        .dyn_into::<HtmlCanvasElement>()
+        .ok()?;
+    canvas.set_width(1);
+    canvas.set_height(1);
+
+    canvas
+        .get_context("2d")
+        .ok()??
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()
+}
@@ -0,0 +1,530 @@
+use crate::{
+    ImageDimensions, LabelPlacement, Mode, ObjectFit, ObjectPosition, Orientation, Viewport,
+};
+
+/// Gap between a label following the divider and the line.
+const LABEL_GAP: f64 = 12.0;
+
+/// Where an image ends up inside the container once `object-fit` and
+/// `object-position` have been applied, used to map natural image
+/// coordinates to container coordinates and back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub x: f64,
+    pub y: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    /// Position of the pane the image is displayed in, added after zoom and
+    /// pan are applied.
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+impl Placement {
+    #[must_use]
+    pub fn new(
+        natural: ImageDimensions,
+        container_width: f64,
+        container_height: f64,
+        object_fit: ObjectFit,
+        object_position: ObjectPosition,
+    ) -> Self {
+        let natural_width = f64::from(natural.width.max(1));
+        let natural_height = f64::from(natural.height.max(1));
+        let contain = (container_width / natural_width).min(container_height / natural_height);
+
+        let (scale_x, scale_y) = match object_fit {
+            ObjectFit::Fill => (
+                container_width / natural_width,
+                container_height / natural_height,
+            ),
+            ObjectFit::Contain => (contain, contain),
+            ObjectFit::Cover => {
+                let cover =
+                    (container_width / natural_width).max(container_height / natural_height);
+                (cover, cover)
+            }
+            ObjectFit::None => (1.0, 1.0),
+            ObjectFit::ScaleDown => (contain.min(1.0), contain.min(1.0)),
+        };
+
+        Self {
+            x: natural_width.mul_add(-scale_x, container_width) * object_position.x / 100.0,
+            y: natural_height.mul_add(-scale_y, container_height) * object_position.y / 100.0,
+            scale_x,
+            scale_y,
+            offset_x: 0.0,
+            offset_y: 0.0,
+        }
+    }
+
+    /// Moves the image into a pane starting at (`x`, `y`) of the container.
+    #[must_use]
+    pub const fn offset(mut self, x: f64, y: f64) -> Self {
+        self.offset_x = x;
+        self.offset_y = y;
+        self
+    }
+
+    /// Maps a point in natural image pixels to the container, taking the
+    /// current zoom and pan into account.
+    #[must_use]
+    pub fn to_container(&self, viewport: Viewport, x: f64, y: f64) -> (f64, f64) {
+        (
+            x.mul_add(self.scale_x, self.x)
+                .mul_add(viewport.zoom, self.offset_x - viewport.x),
+            y.mul_add(self.scale_y, self.y)
+                .mul_add(viewport.zoom, self.offset_y - viewport.y),
+        )
+    }
+
+    /// Maps a point in the container back to natural image pixels.
+    #[must_use]
+    pub fn to_natural(&self, viewport: Viewport, x: f64, y: f64) -> (f64, f64) {
+        (
+            ((x - self.offset_x + viewport.x) / viewport.zoom - self.x) / self.scale_x,
+            ((y - self.offset_y + viewport.y) / viewport.zoom - self.y) / self.scale_y,
+        )
+    }
+}
+
+/// Area of the container one image is displayed in, in container pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Pane {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Pane {
+    /// Panes of the left and right image. Both fill the container unless
+    /// the images are shown side by side, where they split it along the
+    /// comparison axis starting from the edge `orientation` starts at.
+    pub(crate) fn split(
+        mode: Mode,
+        orientation: Orientation,
+        width: f64,
+        height: f64,
+    ) -> (Self, Self) {
+        let container = Self {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+        };
+
+        let (start, end) = match (mode, orientation.is_horizontal()) {
+            (Mode::SideBySide, true) => (
+                Self {
+                    width: width / 2.0,
+                    ..container
+                },
+                Self {
+                    x: width / 2.0,
+                    width: width / 2.0,
+                    ..container
+                },
+            ),
+            (Mode::SideBySide, false) => (
+                Self {
+                    height: height / 2.0,
+                    ..container
+                },
+                Self {
+                    y: height / 2.0,
+                    height: height / 2.0,
+                    ..container
+                },
+            ),
+            _ => (container, container),
+        };
+
+        if orientation.is_reversed() {
+            (end, start)
+        } else {
+            (start, end)
+        }
+    }
+
+    pub(crate) fn corners(&self) -> Vec<(f64, f64)> {
+        vec![
+            (self.x, self.y),
+            (self.x + self.width, self.y),
+            (self.x + self.width, self.y + self.height),
+            (self.x, self.y + self.height),
+        ]
+    }
+
+    pub(crate) fn contains(&self, x: f64, y: f64) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
+
+/// A split running `angle` degrees clockwise from vertical, so `0` divides
+/// left from right and `90` top from bottom. Slider positions are measured
+/// along the normal of the split, from the first container corner it meets
+/// to the last one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Divider {
+    normal: (f64, f64),
+    min: f64,
+    max: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Divider {
+    pub(crate) fn new(angle: f64, width: f64, height: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let distances = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)]
+            .map(|(x, y)| x.mul_add(cos, y * sin));
+
+        Self {
+            normal: (cos, sin),
+            min: distances.into_iter().fold(f64::INFINITY, f64::min),
+            max: distances.into_iter().fold(f64::NEG_INFINITY, f64::max),
+            width,
+            height,
+        }
+    }
+
+    fn distance(&self, position: f64) -> f64 {
+        (self.max - self.min).mul_add(position, self.min)
+    }
+
+    fn project(&self, x: f64, y: f64) -> f64 {
+        x.mul_add(self.normal.0, y * self.normal.1)
+    }
+
+    /// Slider position of the split passing through the container point
+    /// (`x`, `y`).
+    pub(crate) fn position_at(&self, x: f64, y: f64) -> f64 {
+        let range = self.max - self.min;
+        if range <= 0.0 {
+            return 0.0;
+        }
+
+        ((self.project(x, y) - self.min) / range).clamp(0.0, 1.0)
+    }
+
+    /// Point of the split at `position` closest to the container center.
+    pub(crate) fn center(&self, position: f64) -> (f64, f64) {
+        let (x, y) = (self.width / 2.0, self.height / 2.0);
+        let offset = self.distance(position) - self.project(x, y);
+
+        (
+            offset.mul_add(self.normal.0, x),
+            offset.mul_add(self.normal.1, y),
+        )
+    }
+
+    /// Point `offset` pixels from [`Divider::center`] across the split, on
+    /// the side before it when `offset` is negative.
+    pub(crate) fn beside(&self, position: f64, offset: f64) -> (f64, f64) {
+        let (x, y) = self.center(position);
+
+        (
+            offset.mul_add(self.normal.0, x),
+            offset.mul_add(self.normal.1, y),
+        )
+    }
+
+    /// Distance of the container point (`x`, `y`) along the split at
+    /// `position`, from the point returned by [`Divider::center`].
+    pub(crate) fn along(&self, position: f64, x: f64, y: f64) -> f64 {
+        let (center_x, center_y) = self.center(position);
+        (y - center_y).mul_add(self.normal.0, -(x - center_x) * self.normal.1)
+    }
+
+    /// Where the split at `position` enters and leaves the container, as
+    /// distances along it from [`Divider::center`].
+    pub(crate) fn extent(&self, position: f64) -> (f64, f64) {
+        let polygon = self.polygon(position, true);
+        let distance = self.distance(position);
+
+        polygon
+            .iter()
+            .filter(|(x, y)| (self.project(*x, *y) - distance).abs() < 1e-6)
+            .map(|(x, y)| self.along(position, *x, *y))
+            .fold((0.0, 0.0), |(start, end), along| {
+                (f64::min(start, along), f64::max(end, along))
+            })
+    }
+
+    /// Corners of the part of the container before the split at `position`,
+    /// or after it when `before` is unset.
+    pub(crate) fn polygon(&self, position: f64, before: bool) -> Vec<(f64, f64)> {
+        let distance = self.distance(position);
+        let side = |(x, y): (f64, f64)| {
+            let side = self.project(x, y) - distance;
+            if before {
+                -side
+            } else {
+                side
+            }
+        };
+        let corners = [
+            (0.0, 0.0),
+            (self.width, 0.0),
+            (self.width, self.height),
+            (0.0, self.height),
+        ];
+
+        let mut polygon = Vec::with_capacity(5);
+        for (index, &from) in corners.iter().enumerate() {
+            let to = corners[(index + 1) % corners.len()];
+            let (from_side, to_side) = (side(from), side(to));
+
+            if from_side >= 0.0 {
+                polygon.push(from);
+            }
+
+            // the split crosses this edge
+            if (from_side >= 0.0) != (to_side >= 0.0) {
+                let t = from_side / (from_side - to_side);
+                polygon.push((
+                    (to.0 - from.0).mul_add(t, from.0),
+                    (to.1 - from.1).mul_add(t, from.1),
+                ));
+            }
+        }
+
+        polygon
+    }
+}
+
+/// Where a label sits: the container point (`x`, `y`) it is anchored at, and
+/// how far it is shifted from there as fractions of its own width and height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LabelAnchor {
+    pub x: f64,
+    pub y: f64,
+    pub shift: (f64, f64),
+}
+
+impl LabelAnchor {
+    const fn new(x: f64, y: f64, shift: (f64, f64)) -> Self {
+        Self { x, y, shift }
+    }
+
+    /// Anchored next to a line, with the whole label on the side the normal
+    /// (`x`, `y`) of the line points to whatever its size. The label is
+    /// centred along the line when the line is vertical or horizontal.
+    fn away_from_line((x, y): (f64, f64), normal: (f64, f64)) -> Self {
+        let shift = |component: f64| {
+            if component.abs() < 1e-9 {
+                -0.5
+            } else if component > 0.0 {
+                0.0
+            } else {
+                -1.0
+            }
+        };
+
+        Self::new(x, y, (shift(normal.0), shift(normal.1)))
+    }
+
+    /// Top-left corner of a `width` x `height` label.
+    pub(crate) fn origin(&self, width: f64, height: f64) -> (f64, f64) {
+        (
+            width.mul_add(self.shift.0, self.x),
+            height.mul_add(self.shift.1, self.y),
+        )
+    }
+}
+
+/// Anchors of the labels of the left and right image in a `width` x `height`
+/// container split `split` of the way from its left or top edge, or by
+/// `divider` at the slider position when it is angled.
+pub(crate) fn label_anchors(
+    placement: LabelPlacement,
+    orientation: Orientation,
+    (width, height): (f64, f64),
+    split: f64,
+    divider: Option<(Divider, f64)>,
+) -> (LabelAnchor, LabelAnchor) {
+    let horizontal = orientation.is_horizontal();
+    let (start, end) = (0.05, 0.95);
+    let (top, bottom) = (0.03, 0.97);
+
+    // the label of the image on the left or top edge, then the other one
+    let (first, last) = match (placement, horizontal) {
+        (LabelPlacement::Edges, true) => (
+            LabelAnchor::new(width * start, height / 2.0, (0.0, -0.5)),
+            LabelAnchor::new(width * end, height / 2.0, (-1.0, -0.5)),
+        ),
+        (LabelPlacement::Edges, false) => (
+            LabelAnchor::new(width / 2.0, height * top, (-0.5, 0.0)),
+            LabelAnchor::new(width / 2.0, height * bottom, (-0.5, -1.0)),
+        ),
+        (LabelPlacement::StartCorners, true) => (
+            LabelAnchor::new(width * start, height * top, (0.0, 0.0)),
+            LabelAnchor::new(width * end, height * top, (-1.0, 0.0)),
+        ),
+        (LabelPlacement::StartCorners, false) => (
+            LabelAnchor::new(width * start, height * top, (0.0, 0.0)),
+            LabelAnchor::new(width * start, height * bottom, (0.0, -1.0)),
+        ),
+        (LabelPlacement::EndCorners, true) => (
+            LabelAnchor::new(width * start, height * bottom, (0.0, -1.0)),
+            LabelAnchor::new(width * end, height * bottom, (-1.0, -1.0)),
+        ),
+        (LabelPlacement::EndCorners, false) => (
+            LabelAnchor::new(width * end, height * top, (-1.0, 0.0)),
+            LabelAnchor::new(width * end, height * bottom, (-1.0, -1.0)),
+        ),
+        (LabelPlacement::FollowDivider, _) => match divider {
+            Some((divider, position)) => {
+                let (x, y) = divider.normal;
+                let (before, after) = (
+                    LabelAnchor::away_from_line(divider.beside(position, -LABEL_GAP), (-x, -y)),
+                    LabelAnchor::away_from_line(divider.beside(position, LABEL_GAP), (x, y)),
+                );
+
+                // an angled split keeps the left image before it, whatever
+                // the orientation
+                return (before, after);
+            }
+            None if horizontal => (
+                LabelAnchor::new(width.mul_add(split, -LABEL_GAP), height / 2.0, (-1.0, -0.5)),
+                LabelAnchor::new(width.mul_add(split, LABEL_GAP), height / 2.0, (0.0, -0.5)),
+            ),
+            None => (
+                LabelAnchor::new(width / 2.0, height.mul_add(split, -LABEL_GAP), (-0.5, -1.0)),
+                LabelAnchor::new(width / 2.0, height.mul_add(split, LABEL_GAP), (-0.5, 0.0)),
+            ),
+        },
+    };
+
+    // the left image is revealed from the right or bottom edge
+    if orientation.is_reversed() {
+        (last, first)
+    } else {
+        (first, last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_points(actual: &[(f64, f64)], expected: &[(f64, f64)]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+                "{actual:?} is not {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn positions_round_trip_through_the_center() {
+        for angle in [0.0, 45.0, 90.0, 135.0, -30.0] {
+            let divider = Divider::new(angle, 200.0, 100.0);
+
+            for position in [0.0, 0.1, 0.5, 0.9, 1.0] {
+                let (x, y) = divider.center(position);
+                assert!(
+                    (divider.position_at(x, y) - position).abs() < 1e-9,
+                    "{position} at {angle}°"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn splits_vertically_at_0_degrees() {
+        let divider = Divider::new(0.0, 200.0, 100.0);
+
+        assert_points(&[divider.center(0.25)], &[(50.0, 50.0)]);
+        assert_points(
+            &divider.polygon(0.25, true),
+            &[(0.0, 0.0), (50.0, 0.0), (50.0, 100.0), (0.0, 100.0)],
+        );
+        assert_points(
+            &divider.polygon(0.25, false),
+            &[(50.0, 0.0), (200.0, 0.0), (200.0, 100.0), (50.0, 100.0)],
+        );
+    }
+
+    #[test]
+    fn splits_horizontally_at_90_degrees() {
+        let divider = Divider::new(90.0, 200.0, 100.0);
+
+        assert_points(&[divider.center(0.5)], &[(100.0, 50.0)]);
+        assert_points(
+            &divider.polygon(0.5, true),
+            &[(0.0, 0.0), (200.0, 0.0), (200.0, 50.0), (0.0, 50.0)],
+        );
+        assert_points(
+            &divider.polygon(0.5, false),
+            &[(200.0, 50.0), (200.0, 100.0), (0.0, 100.0), (0.0, 50.0)],
+        );
+    }
+
+    #[test]
+    fn splits_diagonally_at_45_degrees() {
+        let divider = Divider::new(45.0, 100.0, 100.0);
+
+        // the split at a quarter runs from (50, 0) to (0, 50)
+        assert_points(&[divider.center(0.25)], &[(25.0, 25.0)]);
+        assert_points(
+            &divider.polygon(0.25, true),
+            &[(0.0, 0.0), (50.0, 0.0), (0.0, 50.0)],
+        );
+        assert_points(
+            &divider.polygon(0.25, false),
+            &[
+                (50.0, 0.0),
+                (100.0, 0.0),
+                (100.0, 100.0),
+                (0.0, 100.0),
+                (0.0, 50.0),
+            ],
+        );
+        assert!((divider.position_at(100.0, 100.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn anchors_labels_on_the_edges_their_images_start_from() {
+        let (left, right) = label_anchors(
+            LabelPlacement::Edges,
+            Orientation::RightToLeft,
+            (200.0, 100.0),
+            0.5,
+            None,
+        );
+
+        assert_eq!(left, LabelAnchor::new(190.0, 50.0, (-1.0, -0.5)));
+        assert_eq!(right, LabelAnchor::new(10.0, 50.0, (0.0, -0.5)));
+        assert_eq!(left.origin(40.0, 20.0), (150.0, 40.0));
+    }
+
+    #[test]
+    fn anchors_labels_beside_the_divider() {
+        let (left, right) = label_anchors(
+            LabelPlacement::FollowDivider,
+            Orientation::LeftToRight,
+            (200.0, 100.0),
+            0.25,
+            None,
+        );
+        assert_eq!(left, LabelAnchor::new(38.0, 50.0, (-1.0, -0.5)));
+        assert_eq!(right, LabelAnchor::new(62.0, 50.0, (0.0, -0.5)));
+
+        // a quarter of the way along a 45° split of a square
+        let divider = Divider::new(45.0, 100.0, 100.0);
+        let (left, right) = label_anchors(
+            LabelPlacement::FollowDivider,
+            Orientation::LeftToRight,
+            (100.0, 100.0),
+            0.25,
+            Some((divider, 0.25)),
+        );
+        assert_eq!(left.shift, (-1.0, -1.0));
+        assert_eq!(right.shift, (0.0, 0.0));
+        assert!(left.x < 25.0 && left.y < 25.0 && right.x > 25.0 && right.y > 25.0);
+    }
+}
@@ -1,18 +1,62 @@
-use std::{cell::RefCell, option::Option, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    option::Option,
+    rc::Rc,
+    time::Duration,
+};
 
+use controller::Attached;
+pub use controller::CompareImageController;
+use css::{parse_declarations, ParsedCss};
+pub use css::{CssError, CssErrorKind, CssMerge};
 use derive_more::derive::Display;
-use ev::{MouseEvent, TouchEvent};
+use ev::{KeyboardEvent, MouseEvent, TouchEvent, WheelEvent};
+use export::Snapshot;
+pub use export::{ExportOptions, ExportResolution};
+pub use gallery::{CompareImageGallery, ComparePair};
+pub use group::{use_compare_image_group, CompareImageGroup, CompareImageGroupContext};
 use html::{Div, Img};
+use inspector::Sampler;
+pub use inspector::{PixelSample, Rgba};
+pub use layout::Placement;
+use layout::{Divider, Pane};
 use leptos::{
-    component, create_effect, create_node_ref, create_rw_signal, create_signal, ev, html, view,
-    window, window_event_listener, AttributeValue, Callable, Callback, CollectView, HtmlElement,
-    IntoAttribute, IntoView, NodeRef, SignalGet, SignalSet,
+    component, create_effect, create_memo, create_node_ref, create_rw_signal, create_signal,
+    document, ev, html,
+    leptos_dom::{helpers::IntervalHandle, HydrationCtx},
+    logging::warn,
+    on_cleanup, provide_context, request_animation_frame, set_interval_with_handle, store_value,
+    untrack, view, window, window_event_listener, AttributeValue, Callable, Callback, CollectView,
+    For, HtmlElement, IntoAttribute, IntoView, MaybeSignal, Memo, NodeRef, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, View, ViewFn,
+};
+use leptos_use::{
+    use_debounce_fn, use_debounce_fn_with_arg, use_event_listener, use_resize_observer,
 };
-use leptos_use::{use_event_listener, use_resize_observer};
-use style::create_styles;
+pub use persist::{PersistedState, Persistence};
+pub use state::{use_compare_image, SliderState};
+use style::{create_styles, SideInputs, Style, StyleInputs, Styles};
+pub use style::{ElementOverride, StyleOverrides};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::HtmlImageElement;
 
 const WHITE_HEX: &str = "#ffffff";
+const REGION_HEX: &str = "#ff00ff";
 
+mod controller;
+pub mod css;
+#[cfg(feature = "diff")]
+pub mod diff;
+mod export;
+mod fullscreen;
+mod gallery;
+mod group;
+mod inspector;
+mod layout;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod persist;
+mod state;
 pub mod style;
 
 #[derive(Debug, Display, Default)]
@@ -24,48 +68,1106 @@ pub enum AspectRatio {
     Wider,
 }
 
+/// How an image is resized to fit the comparison container, mirroring the
+/// CSS `object-fit` property.
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectFit {
+    #[display("contain")]
+    Contain,
+    #[default]
+    #[display("cover")]
+    Cover,
+    #[display("fill")]
+    Fill,
+    #[display("none")]
+    None,
+    #[display("scale-down")]
+    ScaleDown,
+}
+
+/// Alignment of an image inside the container, mirroring the CSS
+/// `object-position` property. Both axes are percentages.
+#[derive(Debug, Display, Clone, Copy, PartialEq)]
+#[display("{x}% {y}%")]
+pub struct ObjectPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl ObjectPosition {
+    pub const CENTER: Self = Self { x: 50.0, y: 50.0 };
+    pub const TOP_LEFT: Self = Self { x: 0.0, y: 0.0 };
+}
+
+impl Default for ObjectPosition {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+/// How the two images are presented.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The left image is revealed over the right one up to the slider.
+    #[default]
+    Slider,
+    /// Both images are shown whole next to each other, or stacked for
+    /// vertical orientations, sharing zoom and pan.
+    SideBySide,
+    /// Only one image is shown at a time, toggled by a click, the space key
+    /// or every `blink_interval`.
+    Blink,
+    /// The left image is laid over the right one with the opacity set by
+    /// `onion_skin_opacity`, or by the slider when that is `None`.
+    OnionSkin,
+}
+
+/// Colour an image is tinted with, such as red and cyan on the two layers of
+/// an onion skin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tint {
+    Red,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+    Magenta,
+    /// Any hue in degrees.
+    Hue(f64),
+}
+
+impl Tint {
+    #[must_use]
+    pub const fn hue(self) -> f64 {
+        match self {
+            Self::Red => 0.0,
+            Self::Yellow => 60.0,
+            Self::Green => 120.0,
+            Self::Cyan => 180.0,
+            Self::Blue => 240.0,
+            Self::Magenta => 300.0,
+            Self::Hue(hue) => hue,
+        }
+    }
+}
+
+/// Direction the comparison runs in, from the edge the left image is
+/// revealed from towards the right image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl Orientation {
+    /// `RightToLeft` when the document is laid out right to left,
+    /// `LeftToRight` otherwise.
+    #[must_use]
+    pub fn from_document() -> Self {
+        if !cfg!(feature = "ssr") && document().dir().eq_ignore_ascii_case("rtl") {
+            Self::RightToLeft
+        } else {
+            Self::LeftToRight
+        }
+    }
+
+    #[must_use]
+    pub const fn is_horizontal(self) -> bool {
+        matches!(self, Self::LeftToRight | Self::RightToLeft)
+    }
+
+    /// Whether the left image starts from the right or bottom edge.
+    #[must_use]
+    pub const fn is_reversed(self) -> bool {
+        matches!(self, Self::RightToLeft | Self::BottomToTop)
+    }
+}
+
+/// Shape of the built-in slider handle.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HandleVariant {
+    /// A circle with an arrow on each side.
+    #[default]
+    Circle,
+    /// A circle with a chevron on each side.
+    Chevrons,
+    /// A capsule running along the line.
+    Pill,
+    Square,
+    /// No handle, only the line.
+    Line,
+    /// A capsule with grip dots instead of arrows.
+    Grip,
+}
+
+/// Where the handle sits along the slider line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HandlePlacement {
+    Start,
+    #[default]
+    Center,
+    End,
+    /// Follows the pointer while it is over the container.
+    FollowPointer,
+}
+
+/// Where the image labels sit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LabelPlacement {
+    /// Centered on the edge each image is revealed from.
+    #[default]
+    Edges,
+    /// In the top corners, or the left ones when the comparison is vertical.
+    StartCorners,
+    /// In the bottom corners, or the right ones when the comparison is
+    /// vertical.
+    EndCorners,
+    /// Next to the divider, each on the side of its image.
+    FollowDivider,
+}
+
+/// When the image labels get out of the way.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LabelAutoHide {
+    /// While the slider is dragged.
+    #[default]
+    OnDrag,
+    /// While the pointer is over the comparison.
+    OnHover,
+    Never,
+}
+
+/// Natural size of an image in pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Natural sizes of both compared images, reported once both have loaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadedImages {
+    pub left: ImageDimensions,
+    pub right: ImageDimensions,
+}
+
+/// Zoom level and pan offset applied to both images. `x` and `y` are the
+/// offset of the visible area in container pixels at the current zoom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub zoom: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Viewport {
+    /// Scales the zoom by `factor` while keeping the container point
+    /// (`x`, `y`) in place.
+    #[must_use]
+    pub fn zoom_at(self, factor: f64, x: f64, y: f64, max_zoom: f64) -> Self {
+        let zoom = (self.zoom * factor).clamp(1.0, max_zoom.max(1.0));
+        let scale = zoom / self.zoom;
+
+        Self {
+            zoom,
+            x: (x + self.x).mul_add(scale, -x),
+            y: (y + self.y).mul_add(scale, -y),
+        }
+    }
+
+    /// Keeps the pan offset within the zoomed image of a `width` x `height`
+    /// container.
+    #[must_use]
+    pub fn clamp(self, width: f64, height: f64) -> Self {
+        Self {
+            zoom: self.zoom,
+            x: self.x.clamp(0.0, width * (self.zoom - 1.0)),
+            y: self.y.clamp(0.0, height * (self.zoom - 1.0)),
+        }
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            x: 0.0,
+            y: 0.0,
+        }
+    }
+}
+
+/// Axis-aligned rectangle in natural image pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    /// Smallest region containing both `self` and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Self {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+
+    /// Grows the region by `by` pixels on every side.
+    #[must_use]
+    pub fn expand(&self, by: u32) -> Self {
+        let x = self.x.saturating_sub(by);
+        let y = self.y.saturating_sub(by);
+
+        Self {
+            x,
+            y,
+            width: self.x + self.width + by - x,
+            height: self.y + self.height + by - y,
+        }
+    }
+
+    /// Whether the two regions share at least one pixel.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// Which image an [`Annotation`] is shown on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationSide {
+    Left,
+    Right,
+    #[default]
+    Both,
+}
+
+/// A marker anchored to a point in natural image pixels that shows `content`
+/// as a tooltip when hovered or tapped.
+#[derive(Clone)]
+pub struct Annotation {
+    pub x: f64,
+    pub y: f64,
+    pub side: AnnotationSide,
+    pub content: ViewFn,
+}
+
+impl Annotation {
+    #[must_use]
+    pub fn new(x: f64, y: f64, content: impl Into<ViewFn>) -> Self {
+        Self {
+            x,
+            y,
+            side: AnnotationSide::Both,
+            content: content.into(),
+        }
+    }
+
+    #[must_use]
+    pub const fn side(mut self, side: AnnotationSide) -> Self {
+        self.side = side;
+        self
+    }
+}
+
+/// Measured size of the comparison container in CSS pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ContainerSize {
+    pub width: f64,
+    pub height: f64,
+}
+
 #[component]
 #[must_use]
 pub fn LeptosCompareImage(
+    #[prop(default = None, into)] angle: Option<f64>,
+    #[prop(default = Vec::new().into(), into)] annotations: MaybeSignal<Vec<Annotation>>,
     #[prop(default = AspectRatio::Taller)] aspect_ratio: AspectRatio,
-    #[prop(default = None)] handle: Option<()>,
+    #[prop(default = None.into(), into)] blink_interval: MaybeSignal<Option<f64>>,
+    #[prop(default = None, into)] caption: Option<String>,
+    #[prop(default = None)] controller: Option<CompareImageController>,
+    #[prop(default = None, into)] crossorigin: Option<String>,
+    #[prop(default = CssMerge::ComponentWins)] css_merge: CssMerge,
+    #[prop(default = None, into)] description: Option<String>,
+    #[prop(default = None, into)] handle: Option<ViewFn>,
+    #[prop(default = false)] fullscreen_button: bool,
+    #[prop(default = None, into)] fullscreen_button_content: Option<ViewFn>,
+    #[prop(default = HandlePlacement::Center)] handle_placement: HandlePlacement,
     #[prop(default = 40, into)] handle_size: u32,
+    #[prop(default = HandleVariant::Circle)] handle_variant: HandleVariant,
     #[prop(default = false, into)] hover: bool,
+    #[prop(default = None, into)] id: Option<String>,
+    #[prop(default = 0.05, into)] keyboard_step: f64,
+    #[prop(default = LabelAutoHide::OnDrag)] label_auto_hide: LabelAutoHide,
+    #[prop(default = LabelPlacement::Edges)] label_placement: LabelPlacement,
+    #[prop(default = None, into)] label_reveal_threshold: Option<f64>,
+    #[prop(default = None, into)] left_arrow: Option<ViewFn>,
     #[prop(into)] left_image: String,
     #[prop(default = String::new(), into)] left_image_alt: String,
     #[prop(default = None, into)] left_image_css: Option<AttributeValue>,
     #[prop(default = None, into)] left_image_label: Option<String>,
+    #[prop(default = ObjectFit::Cover)] left_image_object_fit: ObjectFit,
+    #[prop(default = ObjectPosition::CENTER)] left_image_object_position: ObjectPosition,
+    #[prop(default = None)] left_image_tint: Option<Tint>,
+    #[prop(default = None, into)] left_label: Option<ViewFn>,
+    #[prop(default = 8.0, into)] max_zoom: f64,
+    #[prop(default = Mode::Slider)] mode: Mode,
+    #[prop(default = false)] native_scale: bool,
+    #[prop(default = None)] on_css_error: Option<Callback<CssError>>,
+    #[prop(default = None)] on_drag_end: Option<Callback<f64>>,
+    #[prop(default = None)] on_drag_start: Option<Callback<f64>>,
+    #[prop(default = None)] on_hover_enter: Option<Callback<()>>,
+    #[prop(default = None)] on_hover_leave: Option<Callback<()>>,
+    #[prop(default = None)] on_images_loaded: Option<Callback<LoadedImages>>,
+    #[prop(default = None)] on_region_click: Option<Callback<Region>>,
+    #[prop(default = None)] on_resize: Option<Callback<ContainerSize>>,
     #[prop(default = None)] on_slider_position_change: Option<Callback<f64>>,
+    #[prop(default = None)] on_slider_position_commit: Option<Callback<f64>>,
+    #[prop(default = None.into(), into)] onion_skin_opacity: MaybeSignal<Option<f64>>,
+    #[prop(default = None)] orientation: Option<Orientation>,
+    #[prop(default = None, into)] overlay_image: Option<String>,
+    #[prop(default = None, into)] persist: Option<Persistence>,
+    #[prop(default = false)] pixel_inspector: bool,
+    #[prop(default = None)] pixel_inspector_view: Option<Callback<PixelSample, View>>,
+    #[prop(default = None, into)] position_change_debounce: Option<f64>,
+    #[prop(default = false)] position_change_throttle: bool,
+    #[prop(default = String::from(REGION_HEX), into)] region_color: String,
+    #[prop(default = Vec::new().into(), into)] regions: MaybeSignal<Vec<Region>>,
+    #[prop(default = None, into)] right_arrow: Option<ViewFn>,
     #[prop(into)] right_image: String,
     #[prop(default = String::new(), into)] right_image_alt: String,
     #[prop(default = None, into)] right_image_css: Option<AttributeValue>,
     #[prop(default = None, into)] right_image_label: Option<String>,
-    #[prop(default = None, into)] skeleton: Option<()>,
+    #[prop(default = ObjectFit::Cover)] right_image_object_fit: ObjectFit,
+    #[prop(default = ObjectPosition::CENTER)] right_image_object_position: ObjectPosition,
+    #[prop(default = None)] right_image_tint: Option<Tint>,
+    #[prop(default = None, into)] right_label: Option<ViewFn>,
+    #[prop(default = None, into)] scores: Option<ViewFn>,
+    #[prop(default = None, into)] skeleton: Option<ViewFn>,
     #[prop(default = String::from(WHITE_HEX), into)] slider_line_color: String,
     #[prop(default = 2.0, into)] slider_line_width: f64,
     #[prop(default = 0.5, into)] slider_position_percentage: f64,
-    #[prop(default = false)] vertical: bool,
+    #[prop(default = StyleOverrides::default())] style_overrides: StyleOverrides,
+    #[prop(default = true)] sync_with_group: bool,
+    #[prop(default = false)] zoomable: bool,
 ) -> impl IntoView {
-    let horizontal = !vertical;
+    let orientation = orientation.unwrap_or_else(Orientation::from_document);
+    let horizontal = orientation.is_horizontal();
+    let reversed = orientation.is_reversed();
+    // the slider position counts from the start edge, this maps it from and
+    // to the left or top edge
+    let from_start = move |position: f64| if reversed { 1.0 - position } else { position };
+    // turning the split around puts the left image on its other side
+    let angle = angle.map(|angle| if reversed { angle + 180.0 } else { angle });
+
+    // native scale pins both images to the top-left corner at 1:1 pixels
+    let (left_image_object_fit, left_image_object_position) = if native_scale {
+        (ObjectFit::None, ObjectPosition::TOP_LEFT)
+    } else {
+        (left_image_object_fit, left_image_object_position)
+    };
+    let (right_image_object_fit, right_image_object_position) = if native_scale {
+        (ObjectFit::None, ObjectPosition::TOP_LEFT)
+    } else {
+        (right_image_object_fit, right_image_object_position)
+    };
+
+    // parsed once, and merged into the styles on every update
+    let (override_styles, override_errors) = Styles::from_overrides(&style_overrides);
+    let override_styles = store_value(override_styles);
+    create_effect(move |_| {
+        for (part, error) in &override_errors {
+            report_css_errors(
+                &format!("style_overrides.{part}"),
+                std::slice::from_ref(error),
+                on_css_error,
+            );
+        }
+    });
+    let overrides = store_value(style_overrides);
+    let class = move |element: fn(&StyleOverrides) -> &ElementOverride| {
+        overrides.with_value(|overrides| element(overrides).class.clone())
+    };
+    let left_image_css = user_css("left_image_css", left_image_css, on_css_error);
+    let right_image_css = user_css("right_image_css", right_image_css, on_css_error);
+
+    if persist.is_some() && id.is_none() {
+        warn!("`persist` requires an `id` to namespace the saved state, ignoring it");
+    }
+    let persistence = store_value(persist.zip(id.clone()));
+    // the hydration key is the same on the server and the client, so the
+    // description keeps its id when hydrating
+    let description_id = description.as_ref().map(|_| {
+        format!(
+            "{}-description",
+            id.clone()
+                .unwrap_or_else(|| format!("leptos-compare-image-{}", HydrationCtx::peek_always()))
+        )
+    });
+    let restored = persistence.with_value(|persistence| {
+        persistence
+            .as_ref()
+            .and_then(|(persistence, id)| persist::load(*persistence, id))
+    });
 
-    let left_image_css = create_rw_signal(left_image_css.into_attribute());
-    let right_image_css = create_rw_signal(right_image_css.into_attribute());
+    let group = use_compare_image_group().filter(|_| sync_with_group);
 
     // 0 to 1
-    let (slider_position, set_slider_position) = create_signal(slider_position_percentage);
+    let position = group.map_or_else(
+        || create_rw_signal(slider_position_percentage),
+        |group| group.position,
+    );
+    let viewport = group
+        .and_then(|group| group.viewport)
+        .unwrap_or_else(|| create_rw_signal(Viewport::default()));
+
+    if let Some(restored) = restored {
+        position.set(restored.position);
+        viewport.set(restored.viewport);
+    }
+
+    let (slider_position, set_slider_position) = position.split();
     let (container_width, set_container_width) = create_signal(0.0);
     let (container_height, set_container_height) = create_signal(0.0);
     let (left_img_loaded, set_left_img_loaded) = create_signal(false);
     let (right_img_loaded, set_right_img_loaded) = create_signal(false);
     let (is_sliding, set_is_sliding) = create_signal(false);
+    let (is_hovered, set_is_hovered) = create_signal(false);
+    let (is_fullscreen, set_is_fullscreen) = create_signal(false);
+    // size of the screen while fullscreen
+    let (frame_size, set_frame_size) = create_signal((0.0, 0.0));
+    // width of the comparison fitted to the screen while fullscreen
+    let (fullscreen_width, set_fullscreen_width) = create_signal(None::<f64>);
+    // which image blink mode currently shows
+    let showing_left = create_rw_signal(true);
+    let toggle_blink = move || showing_left.update(|showing_left| *showing_left = !*showing_left);
+    // whether dragging the slider does anything in the current mode
+    let slider_active = move || match mode {
+        Mode::Slider => true,
+        Mode::OnionSkin => onion_skin_opacity.get().is_none(),
+        Mode::SideBySide | Mode::Blink => false,
+    };
+    let divider = move || {
+        angle
+            .filter(|_| matches!(mode, Mode::Slider | Mode::OnionSkin))
+            .map(|angle| Divider::new(angle, container_width.get(), container_height.get()))
+    };
+    let onion_opacity = move || {
+        onion_skin_opacity
+            .get()
+            .unwrap_or_else(|| slider_position.get())
+            .clamp(0.0, 1.0)
+    };
+    let panes = move || {
+        Pane::split(
+            mode,
+            orientation,
+            container_width.get(),
+            container_height.get(),
+        )
+    };
+    // whether the label of the left and of the right image are shown
+    let labels_visible = move || {
+        let hidden = match label_auto_hide {
+            LabelAutoHide::OnDrag => is_sliding.get(),
+            LabelAutoHide::OnHover => is_hovered.get(),
+            LabelAutoHide::Never => false,
+        };
+        // share of each image in view
+        let (left, right) = match mode {
+            Mode::Slider => (slider_position.get(), 1.0 - slider_position.get()),
+            Mode::OnionSkin => (onion_opacity(), 1.0 - onion_opacity()),
+            Mode::SideBySide => (1.0, 1.0),
+            Mode::Blink if showing_left.get() => (1.0, 0.0),
+            Mode::Blink => (0.0, 1.0),
+        };
+        let revealed =
+            |share: f64| label_reveal_threshold.is_none_or(|threshold| share >= threshold);
+
+        (!hidden && revealed(left), !hidden && revealed(right))
+    };
+
+    let notify_position_change = store_value(position_notifier(
+        on_slider_position_change,
+        position_change_throttle,
+        position_change_debounce,
+    ));
+    let save_state = move || {
+        persistence.with_value(|persistence| {
+            if let Some((persistence, id)) = persistence {
+                persist::save(
+                    *persistence,
+                    id,
+                    PersistedState {
+                        position: slider_position.get_untracked(),
+                        viewport: viewport.get_untracked(),
+                    },
+                );
+            }
+        });
+    };
+    let save_state_debounced = use_debounce_fn(save_state, 250.0);
+
+    let commit_position = move |position: f64| {
+        save_state();
+
+        if let Some(on_slider_position_commit) = on_slider_position_commit {
+            Callable::call(&on_slider_position_commit, position);
+        }
+    };
+    // moves the slider in one step, as the keyboard does
+    let move_slider = move |position: f64| {
+        let position = position.clamp(0.0, 1.0);
+        set_slider_position.set(position);
+        notify_position_change.with_value(|notify| notify(position));
+        commit_position(position);
+    };
 
+    let frame_ref: NodeRef<Div> = create_node_ref();
     let container_ref: NodeRef<Div> = create_node_ref();
-    let right_image_ref = create_node_ref();
-    let left_image_ref = create_node_ref();
+    let right_image_ref: NodeRef<Img> = create_node_ref();
+    let left_image_ref: NodeRef<Img> = create_node_ref();
 
     use_resize_observer(container_ref, move |entry, _| {
-        let current_container_width = entry[0].target().get_bounding_client_rect().width();
-        set_container_width.set(current_container_width);
+        let rect = entry[0].target().get_bounding_client_rect();
+        set_container_width.set(rect.width());
+
+        if let Some(on_resize) = on_resize {
+            Callable::call(
+                &on_resize,
+                ContainerSize {
+                    width: rect.width(),
+                    height: rect.height(),
+                },
+            );
+        }
+    });
+
+    use_resize_observer(frame_ref, move |entry, _| {
+        let rect = entry[0].target().get_bounding_client_rect();
+        set_frame_size.set((rect.width(), rect.height()));
+    });
+
+    _ = use_event_listener(
+        frame_ref,
+        ev::Custom::<ev::Event>::new("fullscreenchange"),
+        move |_| {
+            if let Some(frame) = frame_ref.get_untracked() {
+                set_is_fullscreen.set(fullscreen::is_active(&frame));
+            }
+        },
+    );
+
+    let toggle_fullscreen = move || {
+        if let Some(frame) = frame_ref.get_untracked() {
+            fullscreen::toggle(&frame);
+        }
+    };
+
+    _ = use_event_listener(container_ref, ev::mouseenter, move |_| {
+        set_is_hovered.set(true);
+
+        if let Some(on_hover_enter) = on_hover_enter {
+            Callable::call(&on_hover_enter, ());
+        }
+    });
+
+    _ = use_event_listener(container_ref, ev::mouseleave, move |_| {
+        set_is_hovered.set(false);
+
+        if let Some(on_hover_leave) = on_hover_leave {
+            Callable::call(&on_hover_leave, ());
+        }
+
+        // in hover mode leaving the container is the equivalent of a release
+        if hover {
+            commit_position(slider_position.get_untracked());
+        }
+    });
+
+    _ = use_event_listener(container_ref, ev::keydown, move |e: KeyboardEvent| {
+        if mode == Mode::Blink && e.key() == " " {
+            e.prevent_default();
+            toggle_blink();
+        }
+
+        if !slider_active() {
+            return;
+        }
+
+        let delta = match e.key().as_str() {
+            "ArrowLeft" | "ArrowUp" if reversed => keyboard_step,
+            "ArrowRight" | "ArrowDown" if reversed => -keyboard_step,
+            "ArrowLeft" | "ArrowUp" => -keyboard_step,
+            "ArrowRight" | "ArrowDown" => keyboard_step,
+            "Home" => -1.0,
+            "End" => 1.0,
+            _ => return,
+        };
+        e.prevent_default();
+
+        move_slider(slider_position.get_untracked() + delta);
+    });
+
+    // pointer position a pan started at, with the pane and viewport it pans
+    let pan_start = store_value(None::<(f64, f64, Pane, Viewport)>);
+    // whether the last press moved the viewport, so releasing it is no click
+    let panned = store_value(false);
+
+    _ = use_event_listener(container_ref, ev::click, move |e: MouseEvent| {
+        if panned.get_value() {
+            panned.set_value(false);
+            return;
+        }
+
+        // clicks on regions, annotations or buttons keep their own meaning
+        let on_image = e
+            .target()
+            .is_some_and(|target| target.has_type::<HtmlImageElement>());
+        if mode == Mode::Blink && on_image {
+            toggle_blink();
+        }
+    });
+
+    let blink_timer = store_value(None::<IntervalHandle>);
+    create_effect(move |_| {
+        if let Some(blink_timer) = blink_timer.get_value() {
+            blink_timer.clear();
+        }
+
+        let interval = blink_interval
+            .get()
+            .filter(|interval| mode == Mode::Blink && *interval > 0.0);
+        blink_timer.set_value(interval.and_then(|interval| {
+            set_interval_with_handle(toggle_blink, Duration::from_secs_f64(interval / 1000.0)).ok()
+        }));
+    });
+    on_cleanup(move || {
+        if let Some(blink_timer) = blink_timer.get_value() {
+            blink_timer.clear();
+        }
+    });
+
+    _ = use_event_listener(container_ref, ev::wheel, move |e: WheelEvent| {
+        if !zoomable {
+            return;
+        }
+        e.prevent_default();
+
+        let rect = container_ref
+            .get_untracked()
+            .unwrap()
+            .get_bounding_client_rect();
+        let x = f64::from(e.client_x()) - rect.left();
+        let y = f64::from(e.client_y()) - rect.top();
+        let pane = pane_at(untrack(panes), x, y);
+        let factor = (-e.delta_y() * 0.002).exp();
+        viewport.update(|viewport| {
+            *viewport = viewport
+                .zoom_at(factor, x - pane.x, y - pane.y, max_zoom)
+                .clamp(pane.width, pane.height);
+        });
+        save_state_debounced();
+    });
+
+    // dragging pans a zoomed comparison, holding shift when the drag would
+    // otherwise move the slider
+    _ = use_event_listener(container_ref, ev::mousedown, move |e: MouseEvent| {
+        let viewport = viewport.get_untracked();
+        if !zoomable
+            || viewport.zoom <= 1.0
+            || e.button() != 0
+            || (untrack(slider_active) && !e.shift_key())
+        {
+            return;
+        }
+        // keeps the slider still and the image from being dragged out
+        e.prevent_default();
+
+        let rect = container_ref
+            .get_untracked()
+            .unwrap()
+            .get_bounding_client_rect();
+        let (x, y) = (f64::from(e.client_x()), f64::from(e.client_y()));
+        let pane = pane_at(untrack(panes), x - rect.left(), y - rect.top());
+        pan_start.set_value(Some((x, y, pane, viewport)));
+        panned.set_value(false);
+    });
+
+    let pan_move_handle = window_event_listener(ev::mousemove, move |e| {
+        let Some((x, y, pane, start)) = pan_start.get_value() else {
+            return;
+        };
+
+        viewport.set(
+            Viewport {
+                x: start.x - (f64::from(e.client_x()) - x),
+                y: start.y - (f64::from(e.client_y()) - y),
+                ..start
+            }
+            .clamp(pane.width, pane.height),
+        );
+        panned.set_value(true);
+    });
+    let pan_end_handle = window_event_listener(ev::mouseup, move |_| {
+        if pan_start.get_value().is_some() {
+            pan_start.set_value(None);
+            save_state();
+        }
+    });
+    on_cleanup(move || {
+        pan_move_handle.remove();
+        pan_end_handle.remove();
+    });
+
+    _ = use_event_listener(container_ref, ev::dblclick, move |_| {
+        if zoomable {
+            viewport.set(Viewport::default());
+            save_state();
+        }
+    });
+
+    create_effect(move |was_sliding: Option<bool>| {
+        let sliding = is_sliding.get();
+
+        match (was_sliding, sliding) {
+            (Some(false), true) => {
+                if let Some(on_drag_start) = on_drag_start {
+                    Callable::call(&on_drag_start, slider_position.get_untracked());
+                }
+            }
+            (Some(true), false) => {
+                let position = slider_position.get_untracked();
+                if let Some(on_drag_end) = on_drag_end {
+                    Callable::call(&on_drag_end, position);
+                }
+                commit_position(position);
+            }
+            _ => {}
+        }
+
+        sliding
+    });
+
+    let natural_dimensions = create_rw_signal(LoadedImages::default());
+    let active_annotation = create_rw_signal(None::<usize>);
+    let pixel_sample = create_rw_signal(None::<PixelSample>);
+    // pointer position within its pane, mirrored into the other one
+    let crosshair = create_rw_signal(None::<(f64, f64)>);
+    // pointer distance along the slider line from its middle
+    let pointer_along = create_rw_signal(None::<f64>);
+
+    // sources `on_images_loaded` was last called for, as both images report
+    // loading when they are already complete
+    let loaded_sources = store_value(None::<(String, String)>);
+    let handle_image_loaded = move || {
+        let (Some(left), Some(right)) = (
+            left_image_ref.get_untracked(),
+            right_image_ref.get_untracked(),
+        ) else {
+            return;
+        };
+
+        let loaded = LoadedImages {
+            left: ImageDimensions {
+                width: left.natural_width(),
+                height: left.natural_height(),
+            },
+            right: ImageDimensions {
+                width: right.natural_width(),
+                height: right.natural_height(),
+            },
+        };
+        natural_dimensions.set(loaded);
+
+        if let Some(on_images_loaded) = on_images_loaded {
+            if left.complete()
+                && right.complete()
+                && loaded.left.width > 0
+                && loaded.right.width > 0
+            {
+                let sources = Some((left.current_src(), right.current_src()));
+                if loaded_sources.with_value(|loaded| *loaded != sources) {
+                    loaded_sources.set_value(sources);
+                    Callable::call(&on_images_loaded, loaded);
+                }
+            }
+        }
+    };
+
+    let left_placement = move || {
+        let (pane, _) = panes();
+        Placement::new(
+            natural_dimensions.get().left,
+            pane.width,
+            pane.height,
+            left_image_object_fit,
+            left_image_object_position,
+        )
+        .offset(pane.x, pane.y)
+    };
+
+    let right_placement = move || {
+        let (_, pane) = panes();
+        Placement::new(
+            natural_dimensions.get().right,
+            pane.width,
+            pane.height,
+            right_image_object_fit,
+            right_image_object_position,
+        )
+        .offset(pane.x, pane.y)
+    };
+    let annotations = store_value(annotations);
+    // where an annotation is shown, twice when mirrored side by side
+    let annotation_positions = move |annotation: &Annotation| {
+        let viewport = viewport.get();
+        let position = slider_position.get();
+        let split = if horizontal {
+            container_width.get() * from_start(position)
+        } else {
+            container_height.get() * from_start(position)
+        };
+        let angled = divider();
+        let (left_pane, right_pane) = panes();
+        let is_revealed = |(x, y): (f64, f64), left: bool| {
+            if mode == Mode::SideBySide {
+                return if left { left_pane } else { right_pane }.contains(x, y);
+            }
+            if mode == Mode::Blink {
+                return showing_left.get() == left;
+            }
+            if mode == Mode::OnionSkin {
+                return true;
+            }
+            if let Some(divider) = angled {
+                return (divider.position_at(x, y) < position) == left;
+            }
+            let along = if horizontal { x } else { y };
+            ((along < split) != reversed) == left
+        };
+        let on_left = left_placement().to_container(viewport, annotation.x, annotation.y);
+        let on_right = right_placement().to_container(viewport, annotation.x, annotation.y);
+        let revealed_left = is_revealed(on_left, true).then_some(on_left);
+        let revealed_right = is_revealed(on_right, false).then_some(on_right);
+
+        match annotation.side {
+            AnnotationSide::Left => [revealed_left, None],
+            AnnotationSide::Right => [revealed_right, None],
+            // side by side both images are visible, mirror the marker
+            AnnotationSide::Both if mode == Mode::SideBySide => [revealed_left, revealed_right],
+            AnnotationSide::Both => [revealed_left.or(Some(on_right)), None],
+        }
+    };
+
+    if let Some(controller) = controller {
+        let left_image_label = left_image_label.clone();
+        let right_image_label = right_image_label.clone();
+        let slider_line_color = slider_line_color.clone();
+        let has_left_label_view = left_label.is_some();
+        let has_right_label_view = right_label.is_some();
+
+        controller.attach(Attached {
+            position: slider_position,
+            set_position: Rc::new(move_slider),
+            frame_ref,
+            is_fullscreen,
+            export: Rc::new(move |options| {
+                let (Some(left), Some(right)) = (
+                    left_image_ref.get_untracked(),
+                    right_image_ref.get_untracked(),
+                ) else {
+                    return Err(JsValue::from_str("the compared images are not mounted"));
+                };
+
+                Snapshot {
+                    left_image: &left,
+                    right_image: &right,
+                    left_placement: untrack(left_placement),
+                    right_placement: untrack(right_placement),
+                    // views given as labels cannot be drawn
+                    left_label: left_image_label.as_deref().filter(|_| !has_left_label_view),
+                    right_label: right_image_label
+                        .as_deref()
+                        .filter(|_| !has_right_label_view),
+                    label_placement,
+                    labels_visible: untrack(labels_visible),
+                    container_width: container_width.get_untracked(),
+                    container_height: container_height.get_untracked(),
+                    slider_position: slider_position.get_untracked(),
+                    slider_line_color: &slider_line_color,
+                    slider_line_width,
+                    orientation,
+                    viewport: viewport.get_untracked(),
+                    mode,
+                    showing_left: showing_left.get_untracked(),
+                    left_opacity: if mode == Mode::OnionSkin {
+                        untrack(onion_opacity)
+                    } else {
+                        1.0
+                    },
+                    left_tint: left_image_tint,
+                    right_tint: right_image_tint,
+                    angle,
+                }
+                .draw(options)
+            }),
+        });
+        on_cleanup(move || controller.detach());
+    }
+
+    let focus_region = move |region: Region| {
+        let (_, pane) = untrack(panes);
+        let (width, height) = (pane.width, pane.height);
+        // the viewport is relative to the pane the region is drawn in
+        let placement = untrack(right_placement).offset(0.0, 0.0);
+        let unzoomed = Viewport::default();
+
+        let (left, top) =
+            placement.to_container(unzoomed, f64::from(region.x), f64::from(region.y));
+        let (right, bottom) = placement.to_container(
+            unzoomed,
+            f64::from(region.x + region.width),
+            f64::from(region.y + region.height),
+        );
+
+        // only a zoomable comparison can be zoomed back out
+        let focused = if zoomable {
+            // leave some room around the region
+            let zoom = ((width / (right - left)).min(height / (bottom - top)) * 0.8)
+                .clamp(1.0, max_zoom.max(1.0));
+            let focused = Viewport {
+                zoom,
+                x: (left + right).mul_add(zoom / 2.0, -width / 2.0),
+                y: (top + bottom).mul_add(zoom / 2.0, -height / 2.0),
+            }
+            .clamp(width, height);
+            viewport.set(focused);
+            focused
+        } else {
+            viewport.get_untracked()
+        };
+
+        if mode == Mode::Slider {
+            let (center_x, center_y) = placement.to_container(
+                focused,
+                f64::from(region.x) + f64::from(region.width) / 2.0,
+                f64::from(region.y) + f64::from(region.height) / 2.0,
+            );
+            move_slider(untrack(divider).map_or_else(
+                || {
+                    from_start(if horizontal {
+                        center_x / width
+                    } else {
+                        center_y / height
+                    })
+                },
+                |divider| divider.position_at(center_x, center_y),
+            ));
+        } else {
+            save_state();
+        }
+
+        if let Some(on_region_click) = on_region_click {
+            Callable::call(&on_region_click, region);
+        }
+    };
+
+    // created on the first sample, the canvas does not exist on the server
+    let sampler = store_value(None::<Sampler>);
+    _ = use_event_listener(container_ref, ev::mousemove, move |e: MouseEvent| {
+        if !pixel_inspector {
+            return;
+        }
+        if sampler.with_value(Option::is_none) {
+            sampler.set_value(Sampler::new());
+        }
+
+        let (Some(container), Some(left), Some(right)) = (
+            container_ref.get_untracked(),
+            left_image_ref.get_untracked(),
+            right_image_ref.get_untracked(),
+        ) else {
+            return;
+        };
+
+        let rect = container.get_bounding_client_rect();
+        let x = f64::from(e.client_x()) - rect.left();
+        let y = f64::from(e.client_y()) - rect.top();
+        let (left_pane, right_pane) = untrack(panes);
+        let pane = pane_at((left_pane, right_pane), x, y);
+        let viewport = viewport.get_untracked();
+        // both images are sampled at the same point of their own pane
+        let sample_at = |image: &HtmlImageElement, placement: Placement, other: Pane| {
+            let (x, y) = placement.to_natural(viewport, x - pane.x + other.x, y - pane.y + other.y);
+            sampler
+                .try_update_value(|sampler| sampler.as_mut()?.sample(image, x, y))
+                .flatten()
+        };
+
+        pixel_sample.set(Some(PixelSample {
+            x,
+            y,
+            left: sample_at(&left, untrack(left_placement), left_pane),
+            right: sample_at(&right, untrack(right_placement), right_pane),
+        }));
+    });
+
+    _ = use_event_listener(container_ref, ev::mousemove, move |e: MouseEvent| {
+        if mode != Mode::SideBySide {
+            return;
+        }
+
+        let Some(container) = container_ref.get_untracked() else {
+            return;
+        };
+
+        let rect = container.get_bounding_client_rect();
+        let x = f64::from(e.client_x()) - rect.left();
+        let y = f64::from(e.client_y()) - rect.top();
+        let pane = pane_at(untrack(panes), x, y);
+        crosshair.set(Some((x - pane.x, y - pane.y)));
+    });
+
+    _ = use_event_listener(container_ref, ev::mousemove, move |e: MouseEvent| {
+        if handle_placement != HandlePlacement::FollowPointer {
+            return;
+        }
+
+        let Some(container) = container_ref.get_untracked() else {
+            return;
+        };
+
+        let rect = container.get_bounding_client_rect();
+        let x = f64::from(e.client_x()) - rect.left();
+        let y = f64::from(e.client_y()) - rect.top();
+        let along = match untrack(divider) {
+            Some(divider) => divider.along(slider_position.get_untracked(), x, y),
+            None if horizontal => y - rect.height() / 2.0,
+            None => x - rect.width() / 2.0,
+        };
+        pointer_along.set(Some(along));
+    });
+
+    _ = use_event_listener(container_ref, ev::mouseleave, move |_| {
+        pixel_sample.set(None);
+        crosshair.set(None);
+        pointer_along.set(None);
     });
 
     create_effect(move |_| {
@@ -88,6 +1190,43 @@ pub fn LeptosCompareImage(
 
     let all_images_loaded = move || right_img_loaded.get() && left_img_loaded.get();
 
+    provide_context(SliderState {
+        position: slider_position.into(),
+        is_sliding: is_sliding.into(),
+        is_loaded: Signal::derive(all_images_loaded),
+        orientation,
+    });
+
+    // slider position under the container point (`x`, `y`)
+    let position_at = move |x: f64, y: f64| {
+        if let Some(divider) = divider() {
+            return divider.position_at(x, y);
+        }
+
+        let mut pos = if horizontal { x } else { y };
+
+        let min_pos = 0.0 + slider_line_width / 2.0;
+        let max_pos = if horizontal {
+            container_width.get() - slider_line_width / 2.0
+        } else {
+            container_height.get() - slider_line_width / 2.0
+        };
+
+        if pos < min_pos {
+            pos = min_pos;
+        }
+
+        if pos > max_pos {
+            pos = max_pos;
+        }
+
+        from_start(if horizontal {
+            pos / container_width.get()
+        } else {
+            pos / container_height.get()
+        })
+    };
+
     create_effect(move |_| {
         let window_touchmove_listener = Rc::new(RefCell::new(None));
         let window_mousemove_listener = Rc::new(RefCell::new(None));
@@ -112,45 +1251,14 @@ pub fn LeptosCompareImage(
             let cursor_y_from_window =
                 f64::from(cursor_y_from_viewport) - window().page_y_offset().unwrap();
 
-            let image_position =
-                (right_image_ref.get().unwrap() as HtmlElement<Img>).get_bounding_client_rect();
-            let mut pos = if horizontal {
-                cursor_x_from_window - image_position.left()
-            } else {
-                cursor_y_from_window - image_position.top()
-            };
-
-            let min_pos = 0.0 + slider_line_width / 2.0;
-            let max_pos = if horizontal {
-                container_width.get() - slider_line_width / 2.0
-            } else {
-                container_height.get() - slider_line_width / 2.0
-            };
-
-            if pos < min_pos {
-                pos = min_pos;
-            }
-
-            if pos > max_pos {
-                pos = max_pos;
-            }
-
-            if horizontal {
-                set_slider_position.set(pos / container_width.get());
-            } else {
-                set_slider_position.set(pos / container_height.get());
-            }
+            let image_position = container_ref.get().unwrap().get_bounding_client_rect();
+            let position = position_at(
+                cursor_x_from_window - image_position.left(),
+                cursor_y_from_window - image_position.top(),
+            );
 
-            if let Some(on_slider_position_change) = on_slider_position_change {
-                if horizontal {
-                    leptos::Callable::call(&on_slider_position_change, pos / container_width.get());
-                } else {
-                    leptos::Callable::call(
-                        &on_slider_position_change,
-                        pos / container_height.get(),
-                    );
-                }
-            }
+            set_slider_position.set(position);
+            notify_position_change.with_value(|notify| notify(position));
         };
 
         let handle_sliding_mouse = move |event: MouseEvent| {
@@ -165,42 +1273,14 @@ pub fn LeptosCompareImage(
             let cursor_y_from_window =
                 f64::from(cursor_y_from_viewport) - window().page_y_offset().unwrap();
 
-            let image_position =
-                (right_image_ref.get().unwrap() as HtmlElement<Img>).get_bounding_client_rect();
-            let mut pos = if horizontal {
-                cursor_x_from_window - image_position.left()
-            } else {
-                cursor_y_from_window - image_position.top()
-            };
-
-            let min_pos = 0.0 + slider_line_width / 2.0;
-            let max_pos = if horizontal {
-                container_width.get() - slider_line_width / 2.0
-            } else {
-                container_height.get() - slider_line_width / 2.0
-            };
-
-            if pos < min_pos {
-                pos = min_pos;
-            }
+            let image_position = container_ref.get().unwrap().get_bounding_client_rect();
+            let position = position_at(
+                cursor_x_from_window - image_position.left(),
+                cursor_y_from_window - image_position.top(),
+            );
 
-            if pos > max_pos {
-                pos = max_pos;
-            }
-
-            if horizontal {
-                set_slider_position.set(pos / container_width.get());
-            } else {
-                set_slider_position.set(pos / container_height.get());
-            }
-
-            if let Some(on_slider_position_change) = on_slider_position_change {
-                if horizontal {
-                    Callable::call(&on_slider_position_change, pos / container_width.get());
-                } else {
-                    Callable::call(&on_slider_position_change, pos / container_height.get());
-                }
-            }
+            set_slider_position.set(position);
+            notify_position_change.with_value(|notify| notify(position));
         };
 
         let wtl = window_touchmove_listener.clone();
@@ -220,7 +1300,11 @@ pub fn LeptosCompareImage(
         };
 
         let wml = window_mousemove_listener.clone();
-        let start_sliding_mouse = move |_| {
+        let start_sliding_mouse = move |e: MouseEvent| {
+            // the press started a pan
+            if e.default_prevented() {
+                return;
+            }
             set_is_sliding.set(true);
 
             wml.replace(Some(window_event_listener(
@@ -246,7 +1330,7 @@ pub fn LeptosCompareImage(
         };
 
         let mut container_touchstart_handle = None;
-        let window_touchend_handle = if all_images_loaded() {
+        let window_touchend_handle = if slider_active() && all_images_loaded() {
             container_touchstart_handle = Some(use_event_listener(
                 container_ref,
                 ev::touchstart,
@@ -263,25 +1347,30 @@ pub fn LeptosCompareImage(
         let mut container_mousedown_handle = None;
         let mut window_mouseup_handle = None;
 
-        if hover {
-            container_mousemove_handle = Some(use_event_listener(
-                container_ref,
-                ev::mousemove,
-                handle_sliding_mouse,
-            ));
-            container_mouseleave_handle = Some(use_event_listener(
-                container_ref,
-                ev::mouseleave,
-                finish_sliding_desktop,
-            ));
-        } else {
-            container_mousedown_handle = Some(use_event_listener(
-                container_ref,
-                ev::mousedown,
-                start_sliding_mouse,
-            ));
-            window_mouseup_handle =
-                Some(window_event_listener(ev::mouseup, finish_sliding_desktop));
+        match (slider_active(), hover) {
+            (true, true) => {
+                container_mousemove_handle = Some(use_event_listener(
+                    container_ref,
+                    ev::mousemove,
+                    handle_sliding_mouse,
+                ));
+                container_mouseleave_handle = Some(use_event_listener(
+                    container_ref,
+                    ev::mouseleave,
+                    finish_sliding_desktop,
+                ));
+            }
+            (true, false) => {
+                container_mousedown_handle = Some(use_event_listener(
+                    container_ref,
+                    ev::mousedown,
+                    start_sliding_mouse,
+                ));
+                window_mouseup_handle =
+                    Some(window_event_listener(ev::mouseup, finish_sliding_desktop));
+            }
+            // nothing to drag when the slider is not in use
+            (false, _) => {}
         }
 
         let Some(left_image_ref): Option<HtmlElement<Img>> = left_image_ref.get() else {
@@ -301,7 +1390,24 @@ pub fn LeptosCompareImage(
             left_image_width_height_ratio.min(right_image_width_height_ratio)
         };
 
-        let ideal_container_height = container_width.get() * ideal_width_height_ratio as f64;
+        // side by side each image only gets half of the width or height
+        let ideal_width_height_ratio = match (mode, horizontal) {
+            (Mode::SideBySide, true) => ideal_width_height_ratio / 2.0,
+            (Mode::SideBySide, false) => ideal_width_height_ratio * 2.0,
+            _ => ideal_width_height_ratio,
+        };
+
+        // in fullscreen the container is as large as the screen allows
+        // while keeping the shape of the images
+        let ideal_container_height = if is_fullscreen.get() {
+            let (frame_width, frame_height) = frame_size.get();
+            let width = frame_width.min(frame_height / ideal_width_height_ratio);
+            set_fullscreen_width.set(Some(width));
+            width * ideal_width_height_ratio
+        } else {
+            set_fullscreen_width.set(None);
+            container_width.get() * ideal_width_height_ratio as f64
+        };
 
         set_container_height.set(ideal_container_height);
 
@@ -324,138 +1430,616 @@ pub fn LeptosCompareImage(
     });
 
     let styles = Rc::new(move || {
-        create_styles(
-            handle_size,
-            container_height.get(),
-            container_width.get(),
-            slider_position.get(),
+        let left_css = left_image_css.get();
+        let right_css = right_image_css.get();
+        let (left_label_visible, right_label_visible) = labels_visible();
+        let mut styles = create_styles(&StyleInputs {
+            left: SideInputs {
+                css: &left_css.declarations,
+                object_fit: left_image_object_fit,
+                object_position: left_image_object_position,
+                tint: left_image_tint,
+                label_visible: left_label_visible,
+            },
+            right: SideInputs {
+                css: &right_css.declarations,
+                object_fit: right_image_object_fit,
+                object_position: right_image_object_position,
+                tint: right_image_tint,
+                label_visible: right_label_visible,
+            },
+            css_merge,
+            container_width: container_width.get(),
+            container_height: container_height.get(),
+            fullscreen_width: fullscreen_width.get(),
+            orientation,
+            mode,
+            viewport: viewport.get(),
+            slider_position: slider_position.get(),
             slider_line_width,
-            &slider_line_color,
-            is_sliding.get(),
-            horizontal,
+            slider_line_color: &slider_line_color,
+            is_sliding: is_sliding.get(),
             hover,
-            Some(left_image_css.get()),
-            Some(right_image_css.into_attribute()),
-        )
+            angle,
+            showing_left: showing_left.get(),
+            onion_skin_opacity: onion_opacity(),
+            handle_size,
+            handle_variant,
+            handle_placement,
+            pointer_along: pointer_along.get(),
+            label_placement,
+        });
+        override_styles.with_value(|overrides| styles.apply_overrides(overrides));
+        styles
     });
 
-    view! {
+    let comparison = view! {
         <>
             {move || {
                 skeleton
-                    .into_iter()
-                    .filter(|()| !all_images_loaded())
+                    .as_ref()
+                    .filter(|_| !all_images_loaded())
                     .map(|skeleton| {
-                        view! { <div>{skeleton}</div> }
+                        view! { <div>{skeleton.run()}</div> }
                     })
-                    .collect_view()
             }}
+            // shown fullscreen, and holding the controls that are not part of the slider
             <div
+                class=class(|overrides| &overrides.frame)
                 style={
                     let style = styles.clone();
                     move || {
-                        let mut style = style()["container"].as_css();
-                        style
-                            .push_str(
-                                format!(
-                                    "display: {}",
-                                    if all_images_loaded() { "block" } else { "none" },
-                                )
-                                    .as_str(),
-                            );
+                        let mut style = style().frame.as_css();
+                        if !all_images_loaded() {
+                            style.push_str(" display: none;");
+                        }
                         style
                     }
                 }
-                node_ref=container_ref
-                data-testid="container"
+                node_ref=frame_ref
             >
-                <img
-                    on:load=move |_| set_right_img_loaded.set(true)
-                    alt=right_image_alt.clone()
-                    data-testid="right-image"
-                    node_ref=right_image_ref
-                    src=right_image.clone()
+                <div
+                    class=class(|overrides| &overrides.container)
                     style={
                         let style = styles.clone();
-                        move || style()["right_image"].as_css()
+                        move || style().container.as_css()
                     }
-                />
-
-                <img
-                    on:load=move |_| set_left_img_loaded.set(true)
-                    alt=left_image_alt.clone()
-                    data-testid="left-image"
-                    node_ref=left_image_ref
-                    src=left_image.clone()
-                    style={
-                        let style = styles.clone();
-                        move || style()["left_image"].as_css()
+                    node_ref=container_ref
+                    id=id
+                    data-testid="container"
+                    tabindex="0"
+                    role=move || if slider_active() { "slider" } else { "group" }
+                    aria-label=accessible_name(&left_image_alt, &right_image_alt)
+                    aria-describedby=description_id.clone()
+                    aria-orientation=move || {
+                        slider_active().then_some(if horizontal { "horizontal" } else { "vertical" })
                     }
-                />
-                <div style={
-                    let style = styles.clone();
-                    move || style()["slider"].as_css()
-                }>
-                    <div style={
-                        let style = styles.clone();
-                        move || style()["line"].as_css()
-                    } />
+                    aria-valuemin=move || slider_active().then_some("0")
+                    aria-valuemax=move || slider_active().then_some("100")
+                    aria-valuenow=move || {
+                        slider_active().then(|| format!("{:.0}", slider_position.get() * 100.0))
+                    }
+                >
+                    <img
+                        on:load=move |_| {
+                            set_right_img_loaded.set(true);
+                            handle_image_loaded();
+                        }
+                        alt=right_image_alt.clone()
+                        crossorigin=crossorigin.clone()
+                        data-testid="right-image"
+                        node_ref=right_image_ref
+                        src=right_image.clone()
+                        class=class(|overrides| &overrides.right_image)
+                        style={
+                            let style = styles.clone();
+                            move || style().right_image.as_css()
+                        }
+                    />
                     {
                         let style = styles.clone();
-                        move || {
-                            if handle.is_some() {
+                        overlay_image
+                            .map(|overlay_image| {
                                 view! {
-                                    <div style=style()["handle_custom"].as_css()>{handle}</div>
+                                    <img
+                                        alt=""
+                                        aria-hidden="true"
+                                        data-testid="overlay-image"
+                                        src=overlay_image
+                                        class=class(|overrides| &overrides.overlay_image)
+                                        style=move || style().overlay_image.as_css()
+                                    />
                                 }
-                            } else {
+                            })
+                    }
+
+                    <img
+                        on:load=move |_| {
+                            set_left_img_loaded.set(true);
+                            handle_image_loaded();
+                        }
+                        alt=left_image_alt.clone()
+                        crossorigin=crossorigin.clone()
+                        data-testid="left-image"
+                        node_ref=left_image_ref
+                        src=left_image.clone()
+                        class=class(|overrides| &overrides.left_image)
+                        style={
+                            let style = styles.clone();
+                            move || style().left_image.as_css()
+                        }
+                    />
+                    {
+                        let styles = styles.clone();
+                        move || {
+                            slider_active()
+                                .then(|| {
+                                    view! {
+                                        <div
+                                            class=class(|overrides| &overrides.slider)
+                                            style={
+                                                let style = styles.clone();
+                                                move || style().slider.as_css()
+                                            }
+                                        >
+                                            <div
+                                                class=class(|overrides| &overrides.line)
+                                                style={
+                                                    let style = styles.clone();
+                                                    move || style().line.as_css()
+                                                }
+                                            />
+                                            {
+                                                let style = styles.clone();
+                                                let css = move |element: fn(&Styles) -> &Style| {
+                                                    let style = style.clone();
+                                                    move || element(&style()).as_css()
+                                                };
+                                                if let Some(handle) = handle.as_ref() {
+                                                    view! { <div class=class(|overrides| &overrides.handle) style=css(|styles| &styles.handle_custom)>{handle.run()}</div> }
+                                                        .into_view()
+                                                } else {
+                                                    match handle_variant {
+                                                        HandleVariant::Line => ().into_view(),
+                                                        HandleVariant::Grip => {
+                                                            view! {
+                                                                <div class=class(|overrides| &overrides.handle) style=css(|styles| &styles.handle_default)>
+                                                                    <div class=class(|overrides| &overrides.grip_dot) style=css(|styles| &styles.grip_dot) />
+                                                                    <div class=class(|overrides| &overrides.grip_dot) style=css(|styles| &styles.grip_dot) />
+                                                                    <div class=class(|overrides| &overrides.grip_dot) style=css(|styles| &styles.grip_dot) />
+                                                                </div>
+                                                            }
+                                                                .into_view()
+                                                        }
+                                                        _ => {
+                                                            view! {
+                                                                <div class=class(|overrides| &overrides.handle) style=css(|styles| &styles.handle_default)>
+                                                                    {left_arrow
+                                                                        .as_ref()
+                                                                        .map_or_else(
+                                                                            || view! { <div class=class(|overrides| &overrides.left_arrow) style=css(|styles| &styles.left_arrow) /> }.into_view(),
+                                                                            ViewFn::run,
+                                                                        )}
+                                                                    {right_arrow
+                                                                        .as_ref()
+                                                                        .map_or_else(
+                                                                            || view! { <div class=class(|overrides| &overrides.right_arrow) style=css(|styles| &styles.right_arrow) /> }.into_view(),
+                                                                            ViewFn::run,
+                                                                        )}
+                                                                </div>
+                                                            }
+                                                                .into_view()
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            <div
+                                                class=class(|overrides| &overrides.line)
+                                                style={
+                                                    let style = styles.clone();
+                                                    move || style().line.as_css()
+                                                }
+                                            />
+                                        </div>
+                                    }
+                                })
+                        }
+                    }
+                    // labels
+                    {
+                        let style = styles.clone();
+                        let css = move |element: fn(&Styles) -> &Style| {
+                            let style = style.clone();
+                            move || element(&style()).as_css()
+                        };
+                        let label = left_label
+                            .as_ref()
+                            .map(ViewFn::run)
+                            .or_else(|| left_image_label.clone().map(IntoView::into_view));
+                        label
+                            .map(|label| {
                                 view! {
-                                    <div style=style()["handle_default"].as_css()>
-                                        <div style=style()["left_arrow"].as_css() />
-                                        <div style=style()["right_arrow"].as_css() />
+                                    <div
+                                        class=class(|overrides| &overrides.left_label_container)
+                                        style=css(|styles| &styles.left_label_container)
+                                    >
+                                        <div class=class(|overrides| &overrides.left_label) style=css(|styles| &styles.left_label)>{label}</div>
                                     </div>
                                 }
-                            }
-                        }
+                            })
                     }
-                    <div style={
+
+                    {
                         let style = styles.clone();
-                        move || style()["line"].as_css()
-                    } />
-                </div>
-                // labels
-                {
-                    let style = styles.clone();
-                    move || {
-                        left_image_label
-                            .iter()
+                        let css = move |element: fn(&Styles) -> &Style| {
+                            let style = style.clone();
+                            move || element(&style()).as_css()
+                        };
+                        let label = right_label
+                            .as_ref()
+                            .map(ViewFn::run)
+                            .or_else(|| right_image_label.clone().map(IntoView::into_view));
+                        label
                             .map(|label| {
                                 view! {
-                                    <div style=style()["left_label_container"].as_css()>
-                                        <div style=style()["left_label"].as_css()>{label}</div>
+                                    <div
+                                        class=class(|overrides| &overrides.right_label_container)
+                                        style=css(|styles| &styles.right_label_container)
+                                    >
+                                        <div class=class(|overrides| &overrides.right_label) style=css(|styles| &styles.right_label)>{label}</div>
                                     </div>
                                 }
                             })
-                            .collect_view()
                     }
-                }
 
-                {
-                    let style = styles.clone();
-                    move || {
-                        right_image_label
-                            .iter()
-                            .map(|label| {
+                    <div
+                        class=class(|overrides| &overrides.regions)
+                        style={
+                            let style = styles.clone();
+                            move || style().regions.as_css()
+                        }
+                    >
+                        {move || {
+                            let placement = right_placement();
+                            let viewport = viewport.get();
+                            let region_color = region_color.clone();
+                            regions
+                                .get()
+                                .into_iter()
+                                .map(|region| {
+                                    let (left, top) = placement
+                                        .to_container(viewport, f64::from(region.x), f64::from(region.y));
+                                    let (right, bottom) = placement
+                                        .to_container(
+                                            viewport,
+                                            f64::from(region.x + region.width),
+                                            f64::from(region.y + region.height),
+                                        );
+                                    view! {
+                                        <div
+                                            data-testid="region"
+                                            style=style::region(
+                                                    left,
+                                                    top,
+                                                    right - left,
+                                                    bottom - top,
+                                                    &region_color,
+                                                    zoomable,
+                                                )
+                                                .as_css()
+                                            on:mousedown=|e| e.stop_propagation()
+                                            on:touchstart=|e| e.stop_propagation()
+                                            on:click=move |_| focus_region(region)
+                                        />
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </div>
+
+                    <div
+                        class=class(|overrides| &overrides.annotations)
+                        style={
+                            let style = styles.clone();
+                            move || style().annotations.as_css()
+                        }
+                    >
+                        <For
+                            each=move || {
+                                let len = annotations.with_value(|annotations| annotations.with(Vec::len));
+                                (0..len).flat_map(|index| [(index, 0), (index, 1)])
+                            }
+                            key=|marker| *marker
+                            children={
+                                let style = styles.clone();
+                                move |(index, slot)| {
+                                    let style = style.clone();
+                                    let position = move || {
+                                        annotations
+                                            .with_value(|annotations| {
+                                                annotations
+                                                    .with(|annotations| {
+                                                        annotations.get(index).map(annotation_positions)
+                                                    })
+                                            })
+                                            .and_then(|positions| positions[slot])
+                                    };
+                                    let content = move || {
+                                        annotations
+                                            .with_value(|annotations| {
+                                                annotations
+                                                    .with(|annotations| {
+                                                        annotations.get(index).map(|annotation| annotation.content.clone())
+                                                    })
+                                            })
+                                    };
+                                    view! {
+                                        <div
+                                            data-testid="annotation"
+                                            style=move || style::annotation_marker(position()).as_css()
+                                            on:mouseenter=move |_| {
+                                                active_annotation.set(Some(index));
+                                            }
+                                            on:mouseleave=move |_| active_annotation.set(None)
+                                            on:mousedown=|e| e.stop_propagation()
+                                            on:touchstart=|e| e.stop_propagation()
+                                            on:click=move |_| {
+                                                active_annotation
+                                                    .update(|active| {
+                                                        *active = if *active == Some(index) {
+                                                            None
+                                                        } else {
+                                                            Some(index)
+                                                        };
+                                                    });
+                                            }
+                                        >
+                                            {move || {
+                                                (active_annotation.get() == Some(index))
+                                                    .then(content)
+                                                    .flatten()
+                                                    .map(|content| {
+                                                        view! {
+                                                            <div
+                                                                class=class(|overrides| &overrides.annotation_tooltip)
+                                                                style=style().annotation_tooltip.as_css()
+                                                            >
+                                                                {content.run()}
+                                                            </div>
+                                                        }
+                                                    })
+                                            }}
+                                        </div>
+                                    }
+                                }
+                            }
+                        />
+                    </div>
+
+                    {
+                        move || {
+                            crosshair
+                                .get()
+                                .map(|(x, y)| {
+                                    let (left_pane, right_pane) = panes();
+                                    [left_pane, right_pane]
+                                        .into_iter()
+                                        .map(|pane| {
+                                            view! {
+                                                <div
+                                                    data-testid="crosshair"
+                                                    style=style::crosshair_line(
+                                                            pane.x + x,
+                                                            pane.y,
+                                                            1.0,
+                                                            pane.height,
+                                                        )
+                                                        .as_css()
+                                                />
+                                                <div
+                                                    data-testid="crosshair"
+                                                    style=style::crosshair_line(
+                                                            pane.x,
+                                                            pane.y + y,
+                                                            pane.width,
+                                                            1.0,
+                                                        )
+                                                        .as_css()
+                                                />
+                                            }
+                                        })
+                                        .collect_view()
+                                })
+                        }
+                    }
+
+                    {
+                        move || {
+                            pixel_sample
+                                .get()
+                                .map(|sample| {
+                                    view! {
+                                        <div
+                                            data-testid="pixel-inspector"
+                                            style=style::pixel_inspector(sample.x, sample.y).as_css()
+                                        >
+                                            {match pixel_inspector_view {
+                                                Some(pixel_inspector_view) => {
+                                                    Callable::call(&pixel_inspector_view, sample)
+                                                }
+                                                None => default_pixel_inspector(sample),
+                                            }}
+                                        </div>
+                                    }
+                                })
+                        }
+                    }
+
+                    {
+                        let style = styles.clone();
+                        scores
+                            .map(|scores| {
                                 view! {
-                                    <div style=style()["right_label_container"].as_css()>
-                                        <div style=style()["right_label"].as_css()>{label}</div>
+                                    <div
+                                        data-testid="scores"
+                                        class=class(|overrides| &overrides.scores)
+                                        style=move || style().scores.as_css()
+                                    >
+                                        {scores.run()}
                                     </div>
                                 }
                             })
-                            .collect_view()
                     }
-                }
+                </div>
 
+                {
+                    let style = styles.clone();
+                    fullscreen_button
+                        .then(|| {
+                            view! {
+                                <button
+                                    aria-label="Toggle fullscreen"
+                                    data-testid="fullscreen-button"
+                                    class=class(|overrides| &overrides.fullscreen_button)
+                                    style=move || style().fullscreen_button.as_css()
+                                    type="button"
+                                    on:click=move |_| toggle_fullscreen()
+                                >
+                                    {fullscreen_button_content
+                                        .map_or_else(|| "⛶".into_view(), |content| content.run())}
+                                </button>
+                            }
+                        })
+                }
             </div>
         </>
+    };
+
+    if caption.is_none() && description.is_none() {
+        return comparison.into_view();
+    }
+
+    view! {
+        <figure style=style::figure().as_css()>
+            {comparison}
+            {caption.map(|caption| view! { <figcaption>{caption}</figcaption> })}
+            {description
+                .map(|description| {
+                    view! {
+                        <p id=description_id style=style::visually_hidden().as_css()>
+                            {description}
+                        </p>
+                    }
+                })}
+        </figure>
+    }
+    .into_view()
+}
+
+fn default_pixel_inspector(sample: PixelSample) -> View {
+    let describe = |pixel: Option<Rgba>| {
+        pixel.map_or_else(
+            || "n/a".to_string(),
+            |pixel| format!("{} {pixel}", pixel.hex()),
+        )
+    };
+    let delta = sample.delta().map_or_else(
+        || "n/a".to_string(),
+        |[r, g, b, a]| format!("{r:+}, {g:+}, {b:+}, {a:+}"),
+    );
+
+    view! {
+        <div>"L " {describe(sample.left)}</div>
+        <div>"R " {describe(sample.right)}</div>
+        <div>"Δ " {delta}</div>
     }
+    .into_view()
+}
+
+/// The pane under the container point (`x`, `y`), the left one when the
+/// point is outside of both.
+fn pane_at((left, right): (Pane, Pane), x: f64, y: f64) -> Pane {
+    if right.contains(x, y) {
+        right
+    } else {
+        left
+    }
+}
+
+/// Names the comparison after the alt texts of both images.
+fn accessible_name(left_image_alt: &str, right_image_alt: &str) -> Option<String> {
+    match (left_image_alt.is_empty(), right_image_alt.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(left_image_alt.to_string()),
+        (true, false) => Some(right_image_alt.to_string()),
+        (false, false) => Some(format!("{left_image_alt} compared to {right_image_alt}")),
+    }
+}
+
+/// Parses the CSS given to the `prop` prop whenever it changes, warning
+/// about and reporting the parts that had to be skipped.
+fn user_css(
+    prop: &'static str,
+    css: Option<AttributeValue>,
+    on_css_error: Option<Callback<CssError>>,
+) -> Memo<Rc<ParsedCss>> {
+    let css = css.into_attribute();
+    let parsed = create_memo(move |_| {
+        Rc::new(parse_declarations(
+            &css.as_nameless_value_string().unwrap_or_default(),
+        ))
+    });
+
+    create_effect(move |_| report_css_errors(prop, &parsed.get().errors, on_css_error));
+
+    parsed
+}
+
+fn report_css_errors(prop: &str, errors: &[CssError], on_css_error: Option<Callback<CssError>>) {
+    for &error in errors {
+        warn!("skipped part of `{prop}`: {error}");
+        if let Some(on_css_error) = on_css_error {
+            Callable::call(&on_css_error, error);
+        }
+    }
+}
+
+/// Builds the function used to report slider movement, coalescing calls to
+/// one per animation frame or delaying them until movement settles.
+fn position_notifier(
+    on_slider_position_change: Option<Callback<f64>>,
+    throttle: bool,
+    debounce: Option<f64>,
+) -> Rc<dyn Fn(f64)> {
+    let Some(on_slider_position_change) = on_slider_position_change else {
+        return Rc::new(|_| {});
+    };
+
+    if let Some(debounce) = debounce {
+        let debounced = use_debounce_fn_with_arg(
+            move |position: f64| Callable::call(&on_slider_position_change, position),
+            debounce,
+        );
+
+        return Rc::new(move |position| {
+            debounced(position);
+        });
+    }
+
+    if throttle {
+        let pending = Rc::new(Cell::new(None));
+
+        return Rc::new(move |position| {
+            if pending.replace(Some(position)).is_none() {
+                let pending = pending.clone();
+                request_animation_frame(move || {
+                    if let Some(position) = pending.take() {
+                        Callable::call(&on_slider_position_change, position);
+                    }
+                });
+            }
+        });
+    }
+
+    Rc::new(move |position| Callable::call(&on_slider_position_change, position))
 }
@@ -0,0 +1,281 @@
+use std::fmt;
+
+use image::{DynamicImage, GrayImage, RgbImage};
+use leptos::{component, view, IntoView};
+
+use crate::Region;
+
+const MAX_VALUE: f64 = 255.0;
+const SSIM_WINDOW: u32 = 8;
+const SSIM_STRIDE: u32 = 4;
+const SSIM_C1: f64 = (0.01 * MAX_VALUE) * (0.01 * MAX_VALUE);
+const SSIM_C2: f64 = (0.03 * MAX_VALUE) * (0.03 * MAX_VALUE);
+
+/// Similarity scores of two images.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// Mean squared error over the RGB channels, `0` for identical images.
+    pub mse: f64,
+    /// Peak signal-to-noise ratio in decibels, infinite for identical images.
+    pub psnr: f64,
+    /// Mean structural similarity of the luma channel, `1` for identical images.
+    pub ssim: f64,
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MSE {:.2} · ", self.mse)?;
+        if self.psnr.is_finite() {
+            write!(f, "PSNR {:.2} dB · ", self.psnr)?;
+        } else {
+            write!(f, "PSNR ∞ · ")?;
+        }
+        write!(f, "SSIM {:.4}", self.ssim)
+    }
+}
+
+/// Scores of one tile of a [`compare_tiles`] run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileMetrics {
+    pub region: Region,
+    pub metrics: Metrics,
+}
+
+/// Compares two images over the area they share when aligned at their
+/// top-left corner.
+#[must_use]
+pub fn compare(left: &DynamicImage, right: &DynamicImage) -> Metrics {
+    let images = Images::new(left, right);
+    images.metrics(images.bounds())
+}
+
+/// Compares two images tile by tile, tiles on the right and bottom edges may
+/// be smaller than `tile_size`.
+#[must_use]
+pub fn compare_tiles(
+    left: &DynamicImage,
+    right: &DynamicImage,
+    tile_size: u32,
+) -> Vec<TileMetrics> {
+    let images = Images::new(left, right);
+    let bounds = images.bounds();
+    let tile_size = tile_size.max(1);
+
+    (0..bounds.height)
+        .step_by(tile_size as usize)
+        .flat_map(|y| {
+            (0..bounds.width)
+                .step_by(tile_size as usize)
+                .map(move |x| Region {
+                    x,
+                    y,
+                    width: tile_size.min(bounds.width - x),
+                    height: tile_size.min(bounds.height - y),
+                })
+        })
+        .map(|region| TileMetrics {
+            region,
+            metrics: images.metrics(region),
+        })
+        .collect()
+}
+
+/// Displays the scores of a comparison, meant to be passed to the `scores`
+/// prop of `LeptosCompareImage`.
+#[component]
+#[must_use]
+pub fn SimilarityScores(metrics: Metrics) -> impl IntoView {
+    view! { <span data-testid="similarity-scores">{metrics.to_string()}</span> }
+}
+
+struct Images {
+    left: RgbImage,
+    right: RgbImage,
+    left_luma: GrayImage,
+    right_luma: GrayImage,
+}
+
+impl Images {
+    fn new(left: &DynamicImage, right: &DynamicImage) -> Self {
+        Self {
+            left: left.to_rgb8(),
+            right: right.to_rgb8(),
+            left_luma: left.to_luma8(),
+            right_luma: right.to_luma8(),
+        }
+    }
+
+    fn bounds(&self) -> Region {
+        Region {
+            x: 0,
+            y: 0,
+            width: self.left.width().min(self.right.width()),
+            height: self.left.height().min(self.right.height()),
+        }
+    }
+
+    fn metrics(&self, region: Region) -> Metrics {
+        let mse = self.mse(region);
+
+        Metrics {
+            mse,
+            psnr: if mse == 0.0 {
+                f64::INFINITY
+            } else {
+                10.0 * (MAX_VALUE * MAX_VALUE / mse).log10()
+            },
+            ssim: self.ssim(region),
+        }
+    }
+
+    fn mse(&self, region: Region) -> f64 {
+        let mut sum = 0.0;
+        let mut count = 0.0;
+
+        for (x, y) in pixels(region) {
+            let left = self.left.get_pixel(x, y);
+            let right = self.right.get_pixel(x, y);
+
+            for (left, right) in left.0.iter().zip(right.0) {
+                sum += (f64::from(*left) - f64::from(right)).powi(2);
+                count += 1.0;
+            }
+        }
+
+        if count == 0.0 {
+            0.0
+        } else {
+            sum / count
+        }
+    }
+
+    /// Mean SSIM over overlapping windows, regions smaller than a window are
+    /// treated as a single window.
+    fn ssim(&self, region: Region) -> f64 {
+        let window_width = SSIM_WINDOW.min(region.width);
+        let window_height = SSIM_WINDOW.min(region.height);
+
+        if window_width == 0 || window_height == 0 {
+            return 1.0;
+        }
+
+        let mut sum = 0.0;
+        let mut count = 0.0;
+
+        for y in (region.y..=region.y + region.height - window_height).step_by(SSIM_STRIDE as usize)
+        {
+            for x in
+                (region.x..=region.x + region.width - window_width).step_by(SSIM_STRIDE as usize)
+            {
+                sum += self.window_ssim(Region {
+                    x,
+                    y,
+                    width: window_width,
+                    height: window_height,
+                });
+                count += 1.0;
+            }
+        }
+
+        sum / count
+    }
+
+    fn window_ssim(&self, window: Region) -> f64 {
+        let n = f64::from(window.width * window.height);
+        let (mut sum_left, mut sum_right) = (0.0, 0.0);
+        let (mut sum_left_sq, mut sum_right_sq, mut sum_product) = (0.0, 0.0, 0.0);
+
+        for (x, y) in pixels(window) {
+            let left = f64::from(self.left_luma.get_pixel(x, y)[0]);
+            let right = f64::from(self.right_luma.get_pixel(x, y)[0]);
+
+            sum_left += left;
+            sum_right += right;
+            sum_left_sq += left * left;
+            sum_right_sq += right * right;
+            sum_product += left * right;
+        }
+
+        let mean_left = sum_left / n;
+        let mean_right = sum_right / n;
+        let variance_left = mean_left.mul_add(-mean_left, sum_left_sq / n);
+        let variance_right = mean_right.mul_add(-mean_right, sum_right_sq / n);
+        let covariance = mean_left.mul_add(-mean_right, sum_product / n);
+
+        (2.0 * mean_left).mul_add(mean_right, SSIM_C1) * 2.0f64.mul_add(covariance, SSIM_C2)
+            / (mean_left.mul_add(mean_left, mean_right * mean_right) + SSIM_C1)
+            / (variance_left + variance_right + SSIM_C2)
+    }
+}
+
+fn pixels(region: Region) -> impl Iterator<Item = (u32, u32)> {
+    (region.y..region.y + region.height)
+        .flat_map(move |y| (region.x..region.x + region.width).map(move |x| (x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            Rgb([(x * 16) as u8, (y * 16) as u8, ((x + y) * 8) as u8])
+        }))
+    }
+
+    #[test]
+    fn identical_images_match_exactly() {
+        let image = gradient(16, 16);
+        let metrics = compare(&image, &image);
+
+        assert!(metrics.mse.abs() < f64::EPSILON);
+        assert!(metrics.psnr.is_infinite());
+        assert!((metrics.ssim - 1.0).abs() < 1e-9);
+        assert_eq!(metrics.to_string(), "MSE 0.00 · PSNR ∞ · SSIM 1.0000");
+    }
+
+    #[test]
+    fn scores_a_one_pixel_difference() {
+        let left = DynamicImage::ImageRgb8(RgbImage::new(4, 4));
+        let mut right = RgbImage::new(4, 4);
+        right.put_pixel(1, 1, Rgb([255, 255, 255]));
+        let metrics = compare(&left, &DynamicImage::ImageRgb8(right));
+
+        // one of 16 pixels is off by 255 in every channel
+        assert!((metrics.mse - 255.0 * 255.0 / 16.0).abs() < 1e-9);
+        assert!((metrics.psnr - 10.0 * 16f64.log10()).abs() < 1e-9);
+        assert!(metrics.ssim < 1.0);
+    }
+
+    #[test]
+    fn compares_the_shared_area_of_different_sizes() {
+        let left = gradient(4, 4);
+        let mut right = gradient(6, 3).to_rgb8();
+        // outside of the left image
+        right.put_pixel(5, 0, Rgb([255, 0, 0]));
+        let metrics = compare(&left, &DynamicImage::ImageRgb8(right));
+
+        assert!(metrics.mse.abs() < f64::EPSILON);
+        assert!((metrics.ssim - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tiles_cover_the_shared_area() {
+        let tiles = compare_tiles(&gradient(5, 3), &gradient(6, 3), 2);
+        let regions = tiles.iter().map(|tile| tile.region).collect::<Vec<_>>();
+
+        assert_eq!(regions.len(), 6);
+        assert_eq!(
+            regions.last(),
+            Some(&Region {
+                x: 4,
+                y: 2,
+                width: 1,
+                height: 1
+            })
+        );
+        assert!(tiles.iter().all(|tile| tile.metrics.psnr.is_infinite()));
+    }
+}
@@ -0,0 +1,179 @@
+use leptos::{logging::warn, window};
+use wasm_bindgen::JsValue;
+use web_sys::{Url, UrlSearchParams};
+
+use crate::Viewport;
+
+const KEY_PREFIX: &str = "leptos-compare-image";
+
+/// Where the state of a comparison is saved so it survives reloads and can be
+/// shared as a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Persistence {
+    /// A query parameter on the current URL, updated with `history.replaceState`.
+    Query,
+    /// An entry in `localStorage`.
+    LocalStorage,
+}
+
+/// The part of a comparison's state that is persisted.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PersistedState {
+    pub position: f64,
+    pub viewport: Viewport,
+}
+
+impl PersistedState {
+    /// Serialises the state as comma separated `key:value` pairs.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let mut value = format!("p:{:.4}", self.position);
+
+        if self.viewport != Viewport::default() {
+            value.push_str(&format!(
+                ",z:{:.3},x:{:.1},y:{:.1}",
+                self.viewport.zoom, self.viewport.x, self.viewport.y
+            ));
+        }
+
+        value
+    }
+
+    /// Parses a value produced by [`PersistedState::encode`], ignoring unknown
+    /// keys. Values that are not finite numbers make the whole state invalid.
+    #[must_use]
+    pub fn decode(value: &str) -> Option<Self> {
+        let mut position = None;
+        let mut viewport = Viewport::default();
+
+        for (key, value) in value.split(',').filter_map(|pair| pair.split_once(':')) {
+            let value = value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())?;
+            match key.trim() {
+                "p" => position = Some(value.clamp(0.0, 1.0)),
+                "z" => viewport.zoom = value.max(1.0),
+                "x" => viewport.x = value.max(0.0),
+                "y" => viewport.y = value.max(0.0),
+                _ => {}
+            }
+        }
+
+        position.map(|position| Self { position, viewport })
+    }
+}
+
+/// Reads the state saved for the component `id`, if any.
+pub(crate) fn load(persistence: Persistence, id: &str) -> Option<PersistedState> {
+    if cfg!(feature = "ssr") {
+        return None;
+    }
+
+    let key = key(id);
+    let value = match persistence {
+        Persistence::Query => UrlSearchParams::new_with_str(&window().location().search().ok()?)
+            .ok()?
+            .get(&key)?,
+        Persistence::LocalStorage => window().local_storage().ok()??.get_item(&key).ok()??,
+    };
+
+    PersistedState::decode(&value)
+}
+
+/// Saves `state` for the component `id`.
+pub(crate) fn save(persistence: Persistence, id: &str, state: PersistedState) {
+    if cfg!(feature = "ssr") {
+        return;
+    }
+
+    let key = key(id);
+    let value = state.encode();
+    let result = match persistence {
+        Persistence::Query => save_to_query(&key, &value),
+        Persistence::LocalStorage => window()
+            .local_storage()
+            .and_then(|storage| storage.map_or(Ok(()), |storage| storage.set_item(&key, &value))),
+    };
+
+    if let Err(err) = result {
+        warn!("failed to persist comparison state for `{id}`: {err:?}");
+    }
+}
+
+fn save_to_query(key: &str, value: &str) -> Result<(), JsValue> {
+    let url = Url::new(&window().location().href()?)?;
+    url.search_params().set(key, value);
+    window()
+        .history()?
+        .replace_state_with_url(&JsValue::NULL, "", Some(&url.href()))
+}
+
+fn key(id: &str) -> String {
+    format!("{KEY_PREFIX}-{id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_the_position() {
+        let state = PersistedState {
+            position: 0.25,
+            viewport: Viewport::default(),
+        };
+
+        assert_eq!(state.encode(), "p:0.2500");
+        assert_eq!(PersistedState::decode(&state.encode()), Some(state));
+    }
+
+    #[test]
+    fn round_trips_the_viewport() {
+        let state = PersistedState {
+            position: 0.5,
+            viewport: Viewport {
+                zoom: 2.5,
+                x: 10.5,
+                y: 20.0,
+            },
+        };
+
+        assert_eq!(state.encode(), "p:0.5000,z:2.500,x:10.5,y:20.0");
+        assert_eq!(PersistedState::decode(&state.encode()), Some(state));
+    }
+
+    #[test]
+    fn ignores_unknown_keys_and_whitespace() {
+        assert_eq!(
+            PersistedState::decode(" p : 0.3 ,q:7"),
+            Some(PersistedState {
+                position: 0.3,
+                viewport: Viewport::default(),
+            })
+        );
+    }
+
+    #[test]
+    fn clamps_out_of_range_values() {
+        assert_eq!(
+            PersistedState::decode("p:2,z:0.5,x:-3,y:4"),
+            Some(PersistedState {
+                position: 1.0,
+                viewport: Viewport {
+                    zoom: 1.0,
+                    x: 0.0,
+                    y: 4.0,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        for value in ["", "p", "z:2", "p:abc", "p:0.5,z:", "p:NaN", "p:0.5,x:inf"] {
+            assert_eq!(PersistedState::decode(value), None, "{value}");
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use leptos::{use_context, Signal};
+
+use crate::Orientation;
+
+/// Live state of the enclosing `LeptosCompareImage`, for custom handles and
+/// skeletons to render from.
+#[derive(Debug, Clone, Copy)]
+pub struct SliderState {
+    /// Slider position from 0 to 1, measured from the start edge of the
+    /// orientation.
+    pub position: Signal<f64>,
+    pub is_sliding: Signal<bool>,
+    pub is_loaded: Signal<bool>,
+    pub orientation: Orientation,
+}
+
+/// Returns the state of the enclosing `LeptosCompareImage`, if any.
+#[must_use]
+pub fn use_compare_image() -> Option<SliderState> {
+    use_context::<SliderState>()
+}
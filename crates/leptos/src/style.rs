@@ -1,183 +1,504 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-    option::Option,
-    string::String,
+pub use crate::css::Style;
+use crate::{
+    css::{
+        parse_declarations, percent, px, Color, CssError, CssMerge, Declaration, Filter, Length,
+        Property, Transform, Value,
+    },
+    layout::{label_anchors, Divider, LabelAnchor, Pane},
+    HandlePlacement, HandleVariant, LabelPlacement, Mode, ObjectFit, ObjectPosition, Orientation,
+    Tint, Viewport,
 };
 
-use derive_more::derive::{Deref, DerefMut};
-use leptos::{Attribute, IntoAttribute};
+/// Gap between the handle and the end of the line it is placed at.
+const HANDLE_INSET: f64 = 8.0;
+
+/// Hue of `sepia(1)`, the starting point of every tint.
+const SEPIA_HUE: f64 = 38.0;
+
+const SHADOW: &str = "0px 3px 1px -2px rgba(0, 0, 0, 0.2), 0px 2px 2px 0px rgba(0, 0, 0, 0.14), 0px 1px 5px 0px rgba(0, 0, 0, 0.12)";
+
+/// Background of the labels and other overlays.
+const SHADE: Color = Color::Rgba(0, 0, 0, 0.5);
+
+/// Styles of every part of the comparison.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Styles {
+    pub frame: Style,
+    pub container: Style,
+    pub right_image: Style,
+    pub overlay_image: Style,
+    pub left_image: Style,
+    pub slider: Style,
+    pub line: Style,
+    pub handle_custom: Style,
+    pub handle_default: Style,
+    pub left_arrow: Style,
+    pub right_arrow: Style,
+    pub grip_dot: Style,
+    pub left_label_container: Style,
+    pub right_label_container: Style,
+    pub left_label: Style,
+    pub right_label: Style,
+    pub regions: Style,
+    pub annotations: Style,
+    pub annotation_tooltip: Style,
+    pub fullscreen_button: Style,
+    pub scores: Style,
+}
 
-use crate::style;
+/// Extra classes and declarations for one part of the comparison.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ElementOverride {
+    pub class: Option<String>,
+    pub css: Option<String>,
+}
 
-#[derive(Debug, Default, Clone, Deref, DerefMut)]
-pub struct Style(BTreeMap<String, Option<Attribute>>);
+impl ElementOverride {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-impl Style {
     #[must_use]
-    pub fn as_css(&self) -> String {
-        let mut buf = String::new();
-
-        for (index, (key, value)) in <BTreeMap<String, Option<Attribute>> as Clone>::clone(self)
-            .into_iter()
-            .enumerate()
-        {
-            if index != 0 {
-                buf.push(' ');
-            }
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    #[must_use]
+    pub fn css(mut self, css: impl Into<String>) -> Self {
+        self.css = Some(css.into());
+        self
+    }
+}
 
-            if let Some(value) = value {
-                buf.push_str(
-                    format!(
-                        "{key}: {value};",
-                        value = match value {
-                            Attribute::String(value) => value.to_string(),
-                            Attribute::Option(value) => match value {
-                                Some(value) => value.to_string(),
-                                None => "null".to_string(),
-                            },
-                            Attribute::Bool(value) => value.to_string(),
-                            // Attribute::Number(value) => value.to_string(),
-                            Attribute::Fn(_) =>
-                                value.as_nameless_value_string().unwrap().to_string(),
-                        }
-                    )
-                    .as_str(),
-                );
+/// Overrides for the parts of the comparison, one for each field of
+/// [`Styles`]. Their declarations replace those of the component whatever
+/// the `css_merge` strategy is, and are applied after `left_image_css` and
+/// `right_image_css`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StyleOverrides {
+    pub frame: ElementOverride,
+    pub container: ElementOverride,
+    pub right_image: ElementOverride,
+    pub overlay_image: ElementOverride,
+    pub left_image: ElementOverride,
+    pub slider: ElementOverride,
+    pub line: ElementOverride,
+    /// Applies to the custom handle as well as the built-in ones.
+    pub handle: ElementOverride,
+    pub left_arrow: ElementOverride,
+    pub right_arrow: ElementOverride,
+    pub grip_dot: ElementOverride,
+    pub left_label_container: ElementOverride,
+    pub right_label_container: ElementOverride,
+    pub left_label: ElementOverride,
+    pub right_label: ElementOverride,
+    pub regions: ElementOverride,
+    pub annotations: ElementOverride,
+    pub annotation_tooltip: ElementOverride,
+    pub fullscreen_button: ElementOverride,
+    pub scores: ElementOverride,
+}
+
+impl Styles {
+    /// The declarations of each override, parsed once and kept in the style
+    /// it is named after, along with whatever had to be skipped in each part.
+    pub(crate) fn from_overrides(
+        overrides: &StyleOverrides,
+    ) -> (Self, Vec<(&'static str, CssError)>) {
+        let mut errors = Vec::new();
+        let mut parse = |part: &'static str, element: &ElementOverride| {
+            let mut style = Style::new();
+            if let Some(css) = &element.css {
+                let parsed = parse_declarations(css);
+                errors.extend(parsed.errors.iter().map(|&error| (part, error)));
+                style.merge(&parsed.declarations, CssMerge::UserOverrides);
             }
-        }
+            style
+        };
+
+        let handle = parse("handle", &overrides.handle);
+        let styles = Self {
+            frame: parse("frame", &overrides.frame),
+            container: parse("container", &overrides.container),
+            right_image: parse("right_image", &overrides.right_image),
+            overlay_image: parse("overlay_image", &overrides.overlay_image),
+            left_image: parse("left_image", &overrides.left_image),
+            slider: parse("slider", &overrides.slider),
+            line: parse("line", &overrides.line),
+            handle_custom: handle.clone(),
+            handle_default: handle,
+            left_arrow: parse("left_arrow", &overrides.left_arrow),
+            right_arrow: parse("right_arrow", &overrides.right_arrow),
+            grip_dot: parse("grip_dot", &overrides.grip_dot),
+            left_label_container: parse("left_label_container", &overrides.left_label_container),
+            right_label_container: parse("right_label_container", &overrides.right_label_container),
+            left_label: parse("left_label", &overrides.left_label),
+            right_label: parse("right_label", &overrides.right_label),
+            regions: parse("regions", &overrides.regions),
+            annotations: parse("annotations", &overrides.annotations),
+            annotation_tooltip: parse("annotation_tooltip", &overrides.annotation_tooltip),
+            fullscreen_button: parse("fullscreen_button", &overrides.fullscreen_button),
+            scores: parse("scores", &overrides.scores),
+        };
+
+        (styles, errors)
+    }
 
-        buf
+    /// Sets the declarations of each override, as returned by
+    /// [`Styles::from_overrides`], on the style it is named after.
+    pub(crate) fn apply_overrides(&mut self, overrides: &Self) {
+        self.frame.extend(&overrides.frame);
+        self.container.extend(&overrides.container);
+        self.right_image.extend(&overrides.right_image);
+        self.overlay_image.extend(&overrides.overlay_image);
+        self.left_image.extend(&overrides.left_image);
+        self.slider.extend(&overrides.slider);
+        self.line.extend(&overrides.line);
+        self.handle_custom.extend(&overrides.handle_custom);
+        self.handle_default.extend(&overrides.handle_default);
+        self.left_arrow.extend(&overrides.left_arrow);
+        self.right_arrow.extend(&overrides.right_arrow);
+        self.grip_dot.extend(&overrides.grip_dot);
+        self.left_label_container
+            .extend(&overrides.left_label_container);
+        self.right_label_container
+            .extend(&overrides.right_label_container);
+        self.left_label.extend(&overrides.left_label);
+        self.right_label.extend(&overrides.right_label);
+        self.regions.extend(&overrides.regions);
+        self.annotations.extend(&overrides.annotations);
+        self.annotation_tooltip
+            .extend(&overrides.annotation_tooltip);
+        self.fullscreen_button.extend(&overrides.fullscreen_button);
+        self.scores.extend(&overrides.scores);
     }
 }
 
-#[must_use]
-#[allow(clippy::too_many_arguments)]
-pub fn create_styles<'a>(
-    handle_size: u32,
-    container_height: f64,
-    container_width: f64,
-    slider_position: f64,
-    slider_line_width: f64,
-    slider_line_color: &str,
-    is_sliding: bool,
-    horizontal: bool,
-    hover: bool,
-    left_image_css: Option<Attribute>,
-    right_image_css: Option<Attribute>,
-) -> HashMap<&'a str, style::Style> {
-    let mut styles = HashMap::<&'a str, style::Style>::new();
-
-    let left_image_css = left_image_css.map(|css| {
-        css.as_nameless_value_string()
-            .unwrap_or_default()
-            .split(';')
-            .collect::<Vec<&str>>()
-            .iter()
-            .filter_map(|s| s.split_once(':'))
-            .map(|(s1, s2)| (s1.trim(), s2.trim()))
-            .map(|(key, value)| (key.to_string(), value.to_string()))
-            .collect::<HashMap<String, String>>()
-    });
-
-    let right_image_css = right_image_css.map(|css| {
-        css.as_nameless_value_string()
-            .unwrap_or_default()
-            .split(';')
-            .collect::<Vec<&str>>()
-            .iter()
-            .filter_map(|s| s.split_once(':'))
-            .map(|(s1, s2)| (s1.trim(), s2.trim()))
-            .map(|(key, value)| (key.to_string(), value.to_string()))
-            .collect::<HashMap<String, String>>()
-    });
-
-    let container = container(container_height);
-
-    styles.insert("container", container);
-
-    let right_image = right_image(
-        horizontal,
-        container_width,
-        slider_position,
-        container_height,
-        right_image_css,
-    );
+impl From<ObjectFit> for Value {
+    fn from(object_fit: ObjectFit) -> Self {
+        Self::Keyword(match object_fit {
+            ObjectFit::Contain => "contain",
+            ObjectFit::Cover => "cover",
+            ObjectFit::Fill => "fill",
+            ObjectFit::None => "none",
+            ObjectFit::ScaleDown => "scale-down",
+        })
+    }
+}
 
-    styles.insert("right_image", right_image);
+impl From<ObjectPosition> for Value {
+    fn from(object_position: ObjectPosition) -> Self {
+        Self::Lengths(percent(object_position.x), percent(object_position.y))
+    }
+}
 
-    let left_image = left_image(
-        horizontal,
-        container_width,
-        slider_position,
-        container_height,
-        left_image_css,
-    );
+/// What [`create_styles`] needs to know about one of the two images.
+#[derive(Debug, Clone, Copy)]
+pub struct SideInputs<'a> {
+    /// Declarations given by the user, merged with `css_merge`.
+    pub css: &'a [Declaration],
+    pub object_fit: ObjectFit,
+    pub object_position: ObjectPosition,
+    pub tint: Option<Tint>,
+    pub label_visible: bool,
+}
 
-    styles.insert("left_image", left_image);
+/// The state of the comparison [`create_styles`] lays it out from.
+#[derive(Debug, Clone, Copy)]
+pub struct StyleInputs<'a> {
+    pub left: SideInputs<'a>,
+    pub right: SideInputs<'a>,
+    pub css_merge: CssMerge,
+    pub container_width: f64,
+    pub container_height: f64,
+    /// Width of the comparison fitted to the screen while fullscreen.
+    pub fullscreen_width: Option<f64>,
+    pub orientation: Orientation,
+    pub mode: Mode,
+    pub viewport: Viewport,
+    pub slider_position: f64,
+    pub slider_line_width: f64,
+    pub slider_line_color: &'a str,
+    pub is_sliding: bool,
+    pub hover: bool,
+    pub angle: Option<f64>,
+    pub showing_left: bool,
+    pub onion_skin_opacity: f64,
+    pub handle_size: u32,
+    pub handle_variant: HandleVariant,
+    pub handle_placement: HandlePlacement,
+    /// Distance of the pointer along the slider line from its middle.
+    pub pointer_along: Option<f64>,
+    pub label_placement: LabelPlacement,
+}
 
-    let slider = slider(
-        horizontal,
-        hover,
+#[must_use]
+pub fn create_styles(inputs: &StyleInputs) -> Styles {
+    let StyleInputs {
+        left,
+        right,
+        css_merge,
         container_width,
+        container_height,
+        fullscreen_width,
+        orientation,
+        mode,
+        viewport,
         slider_position,
+        slider_line_width,
+        slider_line_color,
+        is_sliding,
+        hover,
+        angle,
+        showing_left,
+        onion_skin_opacity,
         handle_size,
+        handle_variant,
+        handle_placement,
+        pointer_along,
+        label_placement,
+    } = *inputs;
+    let horizontal = orientation.is_horizontal();
+    let reversed = orientation.is_reversed();
+    // distance of the split from the left or top edge, as a fraction
+    let split = if reversed {
+        1.0 - slider_position
+    } else {
+        slider_position
+    };
+    let (left_pane, right_pane) = Pane::split(mode, orientation, container_width, container_height);
+    let divider = angle
+        .filter(|_| matches!(mode, Mode::Slider | Mode::OnionSkin))
+        .map(|angle| {
+            (
+                angle,
+                Divider::new(angle, container_width, container_height),
+            )
+        });
+    // the rotated slider is laid out like a left/right one before turning it
+    let slider_horizontal = horizontal || divider.is_some();
+
+    let container = container(container_height, fullscreen_width);
+
+    let mut right_image = right_image(
+        orientation,
+        container_width,
+        split,
         container_height,
+        (right.object_fit, right.object_position),
+        viewport,
     );
 
-    styles.insert("slider", slider);
+    if mode == Mode::SideBySide {
+        fit_pane(&mut right_image, right_pane, viewport);
+    }
 
-    let line = line(slider_line_color, horizontal, slider_line_width);
+    // the right image shows whole behind the blinking or translucent left one
+    if matches!(mode, Mode::Blink | Mode::OnionSkin) {
+        right_image.remove(Property::Clip);
+    }
 
-    styles.insert("line", line);
+    if let Some((_, divider)) = divider.filter(|_| mode == Mode::Slider) {
+        clip_polygon(
+            &mut right_image,
+            &divider.polygon(slider_position, false),
+            viewport,
+        );
+    }
 
-    let handle_custom = handle_custon();
+    // the overlay shows the right image untinted
+    let mut overlay_image = overlay_image(&right_image);
+    overlay_image.merge(right.css, css_merge);
 
-    styles.insert("handle_custom", handle_custom);
+    // tinting before merging leaves a user `filter` to the merge strategy
+    if let Some(tint) = right.tint {
+        right_image.set(Property::Filter, tint_filter(tint));
+    }
 
-    let handle_default = handle_default(
-        horizontal,
-        slider_line_width,
-        slider_line_color,
-        handle_size,
+    right_image.merge(right.css, css_merge);
+
+    let mut left_image = left_image(
+        orientation,
+        container_width,
+        split,
+        container_height,
+        (left.object_fit, left.object_position),
+        viewport,
     );
 
-    styles.insert("handle_default", handle_default);
+    if mode == Mode::SideBySide {
+        fit_pane(&mut left_image, left_pane, viewport);
+    }
 
-    let left_arrow = left_arrow(handle_size, slider_line_color);
+    if let Some((_, divider)) = divider.filter(|_| mode == Mode::Slider) {
+        clip_polygon(
+            &mut left_image,
+            &divider.polygon(slider_position, true),
+            viewport,
+        );
+    }
+
+    // blinking shows the whole left image or nothing of it
+    if mode == Mode::Blink {
+        left_image.remove(Property::Clip);
+        left_image.set(
+            Property::Visibility,
+            if showing_left { "visible" } else { "hidden" },
+        );
+    }
 
-    styles.insert("left_arrow", left_arrow);
+    // onion skin lays the whole left image over the right one
+    if mode == Mode::OnionSkin {
+        left_image.remove(Property::Clip);
+        left_image.set(Property::Opacity, onion_skin_opacity.clamp(0.0, 1.0));
+    }
 
-    let right_arrow = right_arrow(handle_size, slider_line_color);
+    if let Some(tint) = left.tint {
+        left_image.set(Property::Filter, tint_filter(tint));
+    }
 
-    styles.insert("right_arrow", right_arrow);
+    left_image.merge(left.css, css_merge);
 
-    let left_label = left_label(horizontal, is_sliding);
+    let mut slider = slider(
+        slider_horizontal,
+        hover,
+        container_width,
+        split,
+        handle_size,
+        container_height,
+    );
 
-    styles.insert("left_label", left_label);
+    if let Some((angle, divider)) = divider {
+        rotate_slider(
+            &mut slider,
+            angle,
+            divider.center(slider_position),
+            container_width.hypot(container_height),
+        );
+    }
 
-    let right_label = right_label(horizontal, is_sliding);
+    let line = line(slider_line_color, slider_horizontal, slider_line_width);
 
-    styles.insert("right_label", right_label);
+    let mut handle_custom = handle_custon();
 
-    let left_label_container = left_label_container(
-        horizontal,
-        container_width,
-        slider_position,
-        container_height,
+    let mut handle_default = handle_default(
+        slider_horizontal,
+        slider_line_width,
+        slider_line_color,
+        handle_size,
+        handle_variant,
     );
 
-    styles.insert("left_label_container", left_label_container);
+    if handle_placement != HandlePlacement::Center {
+        // how far the line reaches on either side of the middle of the slider
+        let (start, end) = match divider {
+            Some((_, divider)) => divider.extent(slider_position),
+            None if horizontal => (-container_height / 2.0, container_height / 2.0),
+            None => (-container_width / 2.0, container_width / 2.0),
+        };
+        let reach = handle_length(handle_size, handle_variant) / 2.0 + HANDLE_INSET;
+        let (first, last) = (start + reach, end - reach);
+        let offset = match handle_placement {
+            HandlePlacement::Start => first,
+            HandlePlacement::End => last,
+            HandlePlacement::Center | HandlePlacement::FollowPointer => {
+                pointer_along.unwrap_or_default().max(first).min(last)
+            }
+        };
+
+        place_handle(&mut handle_custom, slider_horizontal, offset, false);
+        place_handle(
+            &mut handle_default,
+            slider_horizontal,
+            offset,
+            !slider_horizontal,
+        );
+    }
 
-    let right_label_container = right_label_container(
-        horizontal,
-        container_width,
-        slider_position,
-        container_height,
+    let (left_arrow, right_arrow) = if handle_variant == HandleVariant::Chevrons {
+        (
+            chevron(handle_size, slider_line_width, slider_line_color, true),
+            chevron(handle_size, slider_line_width, slider_line_color, false),
+        )
+    } else {
+        (
+            left_arrow(handle_size, slider_line_color),
+            right_arrow(handle_size, slider_line_color),
+        )
+    };
+
+    // side by side each label stays within the half of its image
+    let split = if mode == Mode::SideBySide { 0.5 } else { split };
+
+    let start_label_container =
+        left_label_container(horizontal, container_width, split, container_height);
+    let end_label_container =
+        right_label_container(horizontal, container_width, split, container_height);
+    let (mut left_label_container, mut right_label_container) = if reversed {
+        (end_label_container, start_label_container)
+    } else {
+        (start_label_container, end_label_container)
+    };
+
+    // both images are in view, and so are their labels
+    if matches!(mode, Mode::Blink | Mode::OnionSkin) {
+        left_label_container.remove(Property::Clip);
+        right_label_container.remove(Property::Clip);
+    }
+
+    if let Some((_, divider)) = divider.filter(|_| mode == Mode::Slider) {
+        clip_polygon(
+            &mut left_label_container,
+            &divider.polygon(slider_position, true),
+            Viewport::default(),
+        );
+        clip_polygon(
+            &mut right_label_container,
+            &divider.polygon(slider_position, false),
+            Viewport::default(),
+        );
+    }
+
+    let (left_anchor, right_anchor) = label_anchors(
+        label_placement,
+        orientation,
+        (container_width, container_height),
+        split,
+        divider.map(|(_, divider)| (divider, slider_position)),
     );
+    let mut left_label = label(left_anchor, left.label_visible);
+    let mut right_label = label(right_anchor, right.label_visible);
 
-    styles.insert("right_label_container", right_label_container);
+    if mode == Mode::Blink {
+        highlight_label(&mut left_label, showing_left);
+        highlight_label(&mut right_label, !showing_left);
+    }
 
-    styles
+    Styles {
+        frame: frame(fullscreen_width.is_some()),
+        container,
+        right_image,
+        overlay_image,
+        left_image,
+        slider,
+        line,
+        handle_custom,
+        handle_default,
+        left_arrow,
+        right_arrow,
+        grip_dot: grip_dot(handle_size, slider_line_color),
+        left_label_container,
+        right_label_container,
+        left_label,
+        right_label,
+        regions: layer(),
+        annotations: layer(),
+        annotation_tooltip: annotation_tooltip(),
+        fullscreen_button: fullscreen_button(),
+        scores: scores(is_sliding),
+    }
 }
 
 fn right_label_container(
@@ -186,41 +507,18 @@ fn right_label_container(
     slider_position: f64,
     container_height: f64,
 ) -> Style {
-    let mut right_label_container = [
-        ("height", "100%"),
-        ("position", "absolute"),
-        ("width", "100%"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-
-    right_label_container.insert(
-        "clip".to_string(),
-        if horizontal {
-            Some(Attribute::String(
-                format!(
-                    "rect(auto, auto, auto, {:.0}px)",
-                    container_width * slider_position
-                )
-                .into(),
-            ))
-        } else {
-            Some(Attribute::String(
-                format!(
-                    "rect({:.0}px, auto, auto, auto)",
-                    container_height * slider_position
-                )
-                .into(),
-            ))
-        },
-    );
-    right_label_container
+    label_container().with(
+        Property::Clip,
+        clip_rect(
+            horizontal,
+            if horizontal {
+                container_width * slider_position
+            } else {
+                container_height * slider_position
+            },
+            false,
+        ),
+    )
 }
 
 fn left_label_container(
@@ -229,230 +527,306 @@ fn left_label_container(
     slider_position: f64,
     container_height: f64,
 ) -> Style {
-    let mut left_label_container = [
-        ("height", "100%"),
-        ("position", "absolute"),
-        ("width", "100%"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-
-    left_label_container.insert(
-        "clip".to_string(),
-        if horizontal {
-            Some(Attribute::String(
-                format!(
-                    "rect(auto, {:.0}px, auto, auto)",
-                    container_width * slider_position
-                )
-                .into(),
-            ))
-        } else {
-            Some(Attribute::String(
-                format!(
-                    "rect({:.0}px, auto, auto, auto)",
-                    container_height * slider_position
-                )
-                .into(),
-            ))
-        },
-    );
-    left_label_container
-}
-
-fn right_label(horizontal: bool, is_sliding: bool) -> Style {
-    let mut right_label = [
-        ("background", "rgba(0, 0, 0, 0.5)"),
-        ("color", "white"),
-        ("padding", "10px 20px"),
-        ("position", "absolute"),
-        (
-            "transform",
+    label_container().with(
+        Property::Clip,
+        clip_rect(
+            horizontal,
             if horizontal {
-                "translate(0, -50%)"
+                container_width * slider_position
             } else {
-                "translate(-50%, 0)"
+                container_height * slider_position
             },
+            true,
         ),
-        ("transition", "opacity 0.1s ease-out"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-
-    right_label.extend(
-        <BTreeMap<std::string::String, Option<Attribute>> as Clone>::clone(
-            &<style::Style as Clone>::clone(
-                &[
-                    (
-                        "left",
-                        if horizontal {
-                            None
-                        } else {
-                            Some(Attribute::String("50%".to_string().into()))
-                        },
-                    ),
-                    (
-                        "right",
-                        if horizontal {
-                            Some(Attribute::String("5%".to_string().into()))
-                        } else {
-                            None
-                        },
-                    ),
-                    (
-                        "top",
-                        if horizontal {
-                            Some(Attribute::String("50%".to_string().into()))
-                        } else {
-                            None
-                        },
-                    ),
-                    (
-                        "bottom",
-                        if horizontal {
-                            None
-                        } else {
-                            Some(Attribute::String("3%".to_string().into()))
-                        },
-                    ),
-                ]
-                .into_iter()
-                .fold(style::Style::default(), |mut acc, (key, value)| {
-                    acc.insert(key.to_string(), value);
-                    acc
-                }),
+    )
+}
+
+/// Covers the whole container, letting clicks through to the images.
+fn label_container() -> Style {
+    Style::new()
+        .with(Property::Height, percent(100.0))
+        .with(Property::PointerEvents, "none")
+        .with(Property::Position, "absolute")
+        .with(Property::Width, percent(100.0))
+}
+
+/// A label anchored at `anchor`, faded out unless `visible`.
+fn label(anchor: LabelAnchor, visible: bool) -> Style {
+    Style::new()
+        .with(Property::Background, SHADE)
+        .with(Property::Color, Color::WHITE)
+        .with(Property::Left, px(anchor.x))
+        .with(Property::Padding, (px(10.0), px(20.0)))
+        .with(Property::PointerEvents, "auto")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(anchor.y))
+        .with(
+            Property::Transform,
+            Transform::translate(
+                percent(anchor.shift.0 * 100.0),
+                percent(anchor.shift.1 * 100.0),
             ),
-        ),
-    );
+        )
+        .with(Property::Transition, "opacity 0.1s ease-out")
+        .with(Property::WhiteSpace, "nowrap")
+        .with(Property::Opacity, if visible { 1.0 } else { 0.0 })
+}
 
-    right_label.insert(
-        "opacity".to_string(),
-        if is_sliding {
-            Some("0".into_attribute())
-        } else {
-            Some("1".into_attribute())
-        },
-    );
-    right_label
+/// Outline of one changed region, in container pixels. Clicking it zooms in
+/// on it when `zoomable` is set.
+#[must_use]
+pub fn region(left: f64, top: f64, width: f64, height: f64, color: &str, zoomable: bool) -> Style {
+    Style::new()
+        .with(
+            Property::Border,
+            Value::Stroke(px(2.0), "solid", Color::Custom(color.to_string())),
+        )
+        .with(Property::BoxSizing, "border-box")
+        .with(
+            Property::Cursor,
+            if zoomable { "zoom-in" } else { "pointer" },
+        )
+        .with(Property::Height, px(height))
+        .with(Property::Left, px(left))
+        .with(Property::PointerEvents, "auto")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(top))
+        .with(Property::Width, px(width))
 }
 
-fn left_label(horizontal: bool, is_sliding: bool) -> Style {
-    let mut left_label = [
-        ("background", "rgba(0, 0, 0, 0.5)"),
-        ("color", "white"),
-        ("left", if horizontal { "5%" } else { "50%" }),
-        ("padding", "10px 20px"),
-        ("position", "absolute"),
-        ("top", if horizontal { "50%" } else { "3%" }),
-        (
-            "transform",
-            if horizontal {
-                "translate(0, -50%)"
-            } else {
-                "translate(-50%, 0)"
-            },
-        ),
-        ("transition", "opacity 0.1s ease-out"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
+/// Marker of one annotation, centered on its anchor in container pixels or
+/// hidden when the anchor is not revealed.
+#[must_use]
+pub fn annotation_marker(position: Option<(f64, f64)>) -> Style {
+    let Some((left, top)) = position else {
+        return Style::new().with(Property::Display, "none");
+    };
+
+    Style::new()
+        .with(Property::Background, SHADE)
+        .with(
+            Property::Border,
+            Value::Stroke(px(2.0), "solid", Color::WHITE),
+        )
+        .with(Property::BorderRadius, percent(100.0))
+        .with(Property::BoxSizing, "border-box")
+        .with(Property::Cursor, "pointer")
+        .with(Property::Height, px(16.0))
+        .with(Property::Left, px(left))
+        .with(Property::PointerEvents, "auto")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(top))
+        .with(
+            Property::Transform,
+            Transform::translate(percent(-50.0), percent(-50.0)),
+        )
+        .with(Property::Width, px(16.0))
+}
 
-    left_label.insert(
-        "opacity".to_string(),
-        if is_sliding {
-            Some(0.into_attribute())
-        } else {
-            Some(1.into_attribute())
-        },
-    );
-    left_label
+/// CSS filter tinting an image with `tint`. The image is turned to sepia
+/// first so that rotating its hue lands on the requested colour.
+#[must_use]
+pub fn tint_filter(tint: Tint) -> Value {
+    Value::Filter(vec![
+        Filter::Grayscale(1.0),
+        Filter::Sepia(1.0),
+        Filter::Saturate(4.0),
+        Filter::HueRotate((tint.hue() - SEPIA_HUE).round()),
+    ])
+}
+
+/// One line of the cross-hair mirrored into both panes in side-by-side mode.
+#[must_use]
+pub fn crosshair_line(left: f64, top: f64, width: f64, height: f64) -> Style {
+    Style::new()
+        .with(Property::Background, Color::Rgba(255, 255, 255, 0.8))
+        .with(Property::Height, px(height))
+        .with(Property::Left, px(left))
+        .with(Property::MixBlendMode, "difference")
+        .with(Property::PointerEvents, "none")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(top))
+        .with(Property::Width, px(width))
+}
+
+/// Tooltip of the pixel inspector, placed next to the pointer.
+#[must_use]
+pub fn pixel_inspector(left: f64, top: f64) -> Style {
+    Style::new()
+        .with(Property::Background, Color::Rgba(0, 0, 0, 0.75))
+        .with(Property::Color, Color::WHITE)
+        .with(Property::FontFamily, "monospace")
+        .with(Property::Left, px(left + 16.0))
+        .with(Property::Padding, (px(5.0), px(10.0)))
+        .with(Property::PointerEvents, "none")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(top + 16.0))
+        .with(Property::WhiteSpace, "nowrap")
+}
+
+#[must_use]
+pub fn figure() -> Style {
+    Style::new().with(Property::Margin, px(0.0))
+}
+
+/// Keeps an element out of sight while screen readers still announce it.
+#[must_use]
+pub fn visually_hidden() -> Style {
+    Style::new()
+        .with(Property::Clip, Value::Rect([px(0.0); 4]))
+        .with(Property::Height, px(1.0))
+        .with(Property::Margin, px(-1.0))
+        .with(Property::Overflow, "hidden")
+        .with(Property::Padding, px(0.0))
+        .with(Property::Position, "absolute")
+        .with(Property::WhiteSpace, "nowrap")
+        .with(Property::Width, px(1.0))
+}
+
+#[must_use]
+pub fn gallery_nav() -> Style {
+    Style::new()
+        .with(Property::AlignItems, "center")
+        .with(Property::Display, "flex")
+        .with(Property::Gap, px(10.0))
+        .with(Property::JustifyContent, "center")
+        .with(Property::Padding, (px(10.0), px(0.0)))
+}
+
+#[must_use]
+pub fn gallery_thumbnails() -> Style {
+    Style::new()
+        .with(Property::Display, "flex")
+        .with(Property::Gap, px(5.0))
+        .with(Property::JustifyContent, "center")
+        .with(Property::OverflowX, "auto")
+}
+
+#[must_use]
+pub fn gallery_thumbnail(active: bool) -> Style {
+    Style::new()
+        .with(Property::Background, "none")
+        .with(
+            Property::Border,
+            Value::Stroke(
+                px(2.0),
+                "solid",
+                if active {
+                    Color::Hex(0x3b82f6)
+                } else {
+                    Color::TRANSPARENT
+                },
+            ),
+        )
+        .with(Property::Cursor, "pointer")
+        .with(Property::Padding, px(0.0))
+}
+
+#[must_use]
+pub fn gallery_thumbnail_image() -> Style {
+    Style::new()
+        .with(Property::Display, "block")
+        .with(Property::Height, px(48.0))
+        .with(Property::ObjectFit, ObjectFit::Cover)
+        .with(Property::Width, px(64.0))
+}
+
+fn annotation_tooltip() -> Style {
+    Style::new()
+        .with(Property::Background, Color::Rgba(0, 0, 0, 0.75))
+        .with(Property::Bottom, px(20.0))
+        .with(Property::Color, Color::WHITE)
+        .with(Property::Left, percent(50.0))
+        .with(Property::Padding, (px(5.0), px(10.0)))
+        .with(Property::Position, "absolute")
+        .with(
+            Property::Transform,
+            Transform::translate(percent(-50.0), px(0.0)),
+        )
+        .with(Property::WhiteSpace, "nowrap")
+}
+
+fn layer() -> Style {
+    Style::new()
+        .with(Property::Height, percent(100.0))
+        .with(Property::Left, px(0.0))
+        .with(Property::PointerEvents, "none")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, px(0.0))
+        .with(Property::Width, percent(100.0))
+}
+
+fn fullscreen_button() -> Style {
+    Style::new()
+        .with(Property::Background, SHADE)
+        .with(Property::Border, "none")
+        .with(Property::Color, Color::WHITE)
+        .with(Property::Cursor, "pointer")
+        .with(Property::Padding, (px(5.0), px(10.0)))
+        .with(Property::Position, "absolute")
+        .with(Property::Right, px(10.0))
+        .with(Property::Top, px(10.0))
+}
+
+fn scores(is_sliding: bool) -> Style {
+    Style::new()
+        .with(Property::Background, SHADE)
+        .with(Property::Color, Color::WHITE)
+        .with(Property::Left, percent(50.0))
+        .with(Property::Padding, (px(10.0), px(20.0)))
+        .with(Property::PointerEvents, "none")
+        .with(Property::Position, "absolute")
+        .with(Property::Top, percent(3.0))
+        .with(
+            Property::Transform,
+            Transform::translate(percent(-50.0), px(0.0)),
+        )
+        .with(Property::Transition, "opacity 0.1s ease-out")
+        .with(Property::WhiteSpace, "nowrap")
+        .with(Property::Opacity, if is_sliding { 0.0 } else { 1.0 })
 }
 
 fn right_arrow(handle_size: u32, slider_line_color: &str) -> Style {
-    let right_arrow = [
-        (
-            "border",
-            format!("inset {:.0}px rgba(0,0,0,0)", f64::from(handle_size) * 0.15).as_str(),
-        ),
-        (
-            "border-left",
-            format!(
-                "{:.0}px solid {}",
-                f64::from(handle_size) * 0.15,
-                slider_line_color
-            )
-            .as_str(),
-        ),
-        ("height", "0px"),
-        (
-            "margin-right",
-            format!("-{:.0}px", f64::from(handle_size) * 0.25).as_str(),
-        ),
-        ("width", "0px"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-    right_arrow
+    let size = f64::from(handle_size);
+
+    Style::new()
+        .with(
+            Property::Border,
+            Value::Stroke(px(size * 0.15), "inset", Color::Rgba(0, 0, 0, 0.0)),
+        )
+        .with(
+            Property::BorderLeft,
+            Value::Stroke(
+                px(size * 0.15),
+                "solid",
+                Color::Custom(slider_line_color.to_string()),
+            ),
+        )
+        .with(Property::Height, px(0.0))
+        .with(Property::MarginRight, px(-size * 0.25))
+        .with(Property::Width, px(0.0))
 }
 
 fn left_arrow(handle_size: u32, slider_line_color: &str) -> Style {
-    [
-        (
-            "border",
-            format!("inset {:.0}px rgba(0,0,0,0)", f64::from(handle_size) * 0.15).as_str(),
-        ),
-        (
-            "border-right",
-            format!(
-                "{:.0}px solid {}",
-                f64::from(handle_size) * 0.15,
-                slider_line_color
-            )
-            .as_str(),
-        ),
-        ("height", "0px"),
-        (
-            "margin-left",
-            format!("-{:.0}px", f64::from(handle_size) * 0.25).as_str(),
-        ),
-        (
-            "margin-right",
-            format!("{:.0}px", f64::from(handle_size) * 0.25).as_str(),
-        ),
-        ("width", "0px"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    })
+    let size = f64::from(handle_size);
+
+    Style::new()
+        .with(
+            Property::Border,
+            Value::Stroke(px(size * 0.15), "inset", Color::Rgba(0, 0, 0, 0.0)),
+        )
+        .with(
+            Property::BorderRight,
+            Value::Stroke(
+                px(size * 0.15),
+                "solid",
+                Color::Custom(slider_line_color.to_string()),
+            ),
+        )
+        .with(Property::Height, px(0.0))
+        .with(Property::MarginLeft, px(-size * 0.25))
+        .with(Property::MarginRight, px(size * 0.25))
+        .with(Property::Width, px(0.0))
 }
 
 fn handle_default(
@@ -460,98 +834,178 @@ fn handle_default(
     slider_line_width: f64,
     slider_line_color: &str,
     handle_size: u32,
+    variant: HandleVariant,
 ) -> Style {
-    let handle_default = [
-        ("align-items", "center"),
-        (
-            "border",
-            format!("{slider_line_width:.0}px solid {slider_line_color}").as_str(),
-        ),
-        ("border-radius", "100%"),
-        ("box-shadow", "0px 3px 1px -2px rgba(0, 0, 0, 0.2), 0px 2px 2px 0px rgba(0, 0, 0, 0.14), 0px 1px 5px 0px rgba(0, 0, 0, 0.12)"),
-        ("box-sizing", "border-box"),
-        ("display", "flex"),
-        ("flex", "1 0 auto"),
-        ("height", format!("{handle_size}px").as_str()),
-        ("justify-content", "center"),
-        ("width", format!("{handle_size}px").as_str()),
-        ("transform", if horizontal {
-            "none"
-        } else {
-            "rotate(90deg)"
-        })
-    ].iter().fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert((*key).to_string(), Some(Attribute::String((*value).to_string().into())));
-        acc
-    });
+    let size = f64::from(handle_size);
+    let mut handle_default = Style::new()
+        .with(Property::AlignItems, "center")
+        .with(
+            Property::Border,
+            Value::Stroke(
+                px(slider_line_width),
+                "solid",
+                Color::Custom(slider_line_color.to_string()),
+            ),
+        )
+        .with(Property::BorderRadius, percent(100.0))
+        .with(Property::BoxShadow, SHADOW)
+        .with(Property::BoxSizing, "border-box")
+        .with(Property::Display, "flex")
+        .with(Property::Flex, "1 0 auto")
+        .with(Property::Height, px(size))
+        .with(Property::JustifyContent, "center")
+        .with(Property::Width, px(size))
+        .with(
+            Property::Transform,
+            if horizontal {
+                Transform::NONE
+            } else {
+                Transform::NONE.rotate(90.0)
+            },
+        );
+
+    match variant {
+        HandleVariant::Circle | HandleVariant::Chevrons | HandleVariant::Line => {}
+        HandleVariant::Pill => {
+            handle_default.set(Property::BorderRadius, px(size / 2.0));
+            handle_default.set(Property::Height, px(handle_length(handle_size, variant)));
+        }
+        HandleVariant::Square => handle_default.set(Property::BorderRadius, px(4.0)),
+        HandleVariant::Grip => {
+            handle_default.set(Property::BorderRadius, px(size * 0.3));
+            handle_default.set(Property::FlexDirection, "column");
+            handle_default.set(Property::Gap, px(size * 0.1));
+            handle_default.set(Property::Height, px(handle_length(handle_size, variant)));
+            handle_default.set(Property::Width, px(size * 0.6));
+        }
+    }
+
     handle_default
 }
 
+/// Size of the built-in handle along the line.
+fn handle_length(handle_size: u32, variant: HandleVariant) -> f64 {
+    f64::from(handle_size)
+        * match variant {
+            HandleVariant::Pill => 1.5,
+            HandleVariant::Grip => 1.2,
+            _ => 1.0,
+        }
+}
+
+/// Takes the handle out of the flow of the slider and moves it `offset`
+/// pixels along the line from its middle.
+fn place_handle(handle: &mut Style, horizontal: bool, offset: f64, rotate: bool) {
+    let along = Length::Calc(50.0, offset);
+    let (left, top) = if horizontal {
+        (percent(50.0), along)
+    } else {
+        (along, percent(50.0))
+    };
+    let centered = Transform::translate(percent(-50.0), percent(-50.0));
+
+    handle.set(Property::Left, left);
+    handle.set(Property::Position, "absolute");
+    handle.set(Property::Top, top);
+    handle.set(
+        Property::Transform,
+        if rotate {
+            centered.rotate(90.0)
+        } else {
+            centered
+        },
+    );
+}
+
+/// A chevron pointing to the start of the line, or to its end when `start`
+/// is unset.
+fn chevron(
+    handle_size: u32,
+    slider_line_width: f64,
+    slider_line_color: &str,
+    start: bool,
+) -> Style {
+    let size = f64::from(handle_size) * 0.2;
+    let stroke = Value::Stroke(
+        px(slider_line_width),
+        "solid",
+        Color::Custom(slider_line_color.to_string()),
+    );
+    let (drawn, hidden) = if start {
+        (
+            [Property::BorderBottom, Property::BorderLeft],
+            [Property::BorderRight, Property::BorderTop],
+        )
+    } else {
+        (
+            [Property::BorderRight, Property::BorderTop],
+            [Property::BorderBottom, Property::BorderLeft],
+        )
+    };
+
+    let mut chevron = Style::new();
+    for property in drawn {
+        chevron.set(property, stroke.clone());
+    }
+    for property in hidden {
+        chevron.set(property, "none");
+    }
+
+    chevron
+        .with(Property::Height, px(size))
+        .with(Property::Margin, (px(0.0), px(size * 0.5)))
+        .with(Property::Transform, Transform::NONE.rotate(45.0))
+        .with(Property::Width, px(size))
+}
+
+fn grip_dot(handle_size: u32, slider_line_color: &str) -> Style {
+    let size = (f64::from(handle_size) * 0.1).max(2.0).round();
+
+    Style::new()
+        .with(
+            Property::Background,
+            Color::Custom(slider_line_color.to_string()),
+        )
+        .with(Property::BorderRadius, percent(100.0))
+        .with(Property::Height, px(size))
+        .with(Property::Width, px(size))
+}
+
 fn handle_custon() -> Style {
-    let handle_custom = [
-        ("align-items", "center"),
-        ("box-sizing", "border-box"),
-        ("display", "flex"),
-        ("flex", "1 0 auto"),
-        ("height", "auto"),
-        ("justify-content", "center"),
-        ("width", "auto"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-    handle_custom
+    Style::new()
+        .with(Property::AlignItems, "center")
+        .with(Property::BoxSizing, "border-box")
+        .with(Property::Display, "flex")
+        .with(Property::Flex, "1 0 auto")
+        .with(Property::Height, Length::Auto)
+        .with(Property::JustifyContent, "center")
+        .with(Property::Width, Length::Auto)
 }
 
 fn line(slider_line_color: &str, horizontal: bool, slider_line_width: f64) -> Style {
-    let mut line = [
-       ( "background", slider_line_color),
-       ("box-shadow", "0px 3px 1px -2px rgba(0, 0, 0, 0.2), 0px 2px 2px 0px rgba(0, 0, 0, 0.14), 0px 1px 5px 0px rgba(0, 0, 0, 0.12)"),
-       ("flex", "0 1 auto"),
-    ].iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert((*key).to_string(), Some(Attribute::String((*value).to_string().into())));
-        acc
-    });
-
-    line.extend(
-        <BTreeMap<std::string::String, Option<Attribute>> as Clone>::clone(
-            &<style::Style as Clone>::clone(
-                &[
-                    (
-                        "height",
-                        if horizontal {
-                            "100%".to_string()
-                        } else {
-                            format!("{slider_line_width:.0}px")
-                        },
-                    ),
-                    (
-                        "width",
-                        if horizontal {
-                            format!("{slider_line_width:.0}px")
-                        } else {
-                            "100%".to_string()
-                        },
-                    ),
-                ]
-                .iter()
-                .fold(style::Style::default(), |mut acc, (key, value)| {
-                    acc.insert(
-                        (*key).to_string(),
-                        Some(Attribute::String(value.clone().into())),
-                    );
-                    acc
-                }),
-            ),
-        ),
-    );
-    line
+    Style::new()
+        .with(
+            Property::Background,
+            Color::Custom(slider_line_color.to_string()),
+        )
+        .with(Property::BoxShadow, SHADOW)
+        .with(Property::Flex, "0 1 auto")
+        .with(
+            Property::Height,
+            if horizontal {
+                percent(100.0)
+            } else {
+                px(slider_line_width)
+            },
+        )
+        .with(
+            Property::Width,
+            if horizontal {
+                px(slider_line_width)
+            } else {
+                percent(100.0)
+            },
+        )
 }
 
 fn slider(
@@ -562,232 +1016,225 @@ fn slider(
     handle_size: u32,
     container_height: f64,
 ) -> Style {
-    let mut slider = [
-        ("align-items", "center"),
-        ("display", "flex"),
-        ("justify-content", "center"),
-        ("position", "absolute"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
+    let size = f64::from(handle_size);
+    let mut slider = Style::new()
+        .with(Property::AlignItems, "center")
+        .with(Property::Display, "flex")
+        .with(Property::JustifyContent, "center")
+        .with(Property::Position, "absolute")
+        .with(
+            Property::FlexDirection,
+            if horizontal { "column" } else { "row" },
+        )
+        .with(
+            Property::Height,
+            if horizontal { percent(100.0) } else { px(size) },
+        )
+        .with(
+            Property::Width,
+            if horizontal { px(size) } else { percent(100.0) },
         );
-        acc
-    });
-
-    slider.extend(
-        <BTreeMap<std::string::String, Option<Attribute>> as Clone>::clone(
-            &<style::Style as Clone>::clone(
-                &[
-                    (
-                        "flex-direction",
-                        if horizontal {
-                            "column".to_string()
-                        } else {
-                            "row".to_string()
-                        },
-                    ),
-                    (
-                        "height",
-                        if horizontal {
-                            "100%".to_string()
-                        } else {
-                            format!("{handle_size:.0}px")
-                        },
-                    ),
-                    (
-                        "width",
-                        if horizontal {
-                            format!("{handle_size:.0}px")
-                        } else {
-                            "100%".to_string()
-                        },
-                    ),
-                ]
-                .iter()
-                .fold(style::Style::default(), |mut acc, (key, value)| {
-                    acc.insert(
-                        (*key).to_string(),
-                        Some(Attribute::String(value.clone().into())),
-                    );
-                    acc
-                }),
-            ),
-        ),
-    );
 
-    slider.insert(
-        "cursor".to_string(),
-        if !hover && horizontal {
-            Some(Attribute::String("ew-resize".to_string().into()))
-        } else if !hover && !horizontal {
-            Some(Attribute::String("ns-resize".to_string().into()))
-        } else {
-            Some(Attribute::Bool(false))
-        },
-    );
+    if !hover {
+        slider.set(
+            Property::Cursor,
+            if horizontal { "ew-resize" } else { "ns-resize" },
+        );
+    }
 
-    slider.insert(
-        "left".to_string(),
+    slider.set(
+        Property::Left,
         if horizontal {
-            Some(Attribute::String(
-                format!(
-                    "{:.0}px",
-                    container_width.mul_add(slider_position, -(f64::from(handle_size) / 2.0))
-                )
-                .into(),
-            ))
+            px(container_width.mul_add(slider_position, -(size / 2.0)))
         } else {
-            Some(0.into_attribute())
+            px(0.0)
         },
     );
 
-    slider.insert(
-        "top".to_string(),
+    slider.set(
+        Property::Top,
         if horizontal {
-            Some(Attribute::String(leptos::Oco::Borrowed("0")))
+            px(0.0)
         } else {
-            Some(Attribute::String(
-                format!(
-                    "{:.0}px",
-                    container_height.mul_add(slider_position, -(f64::from(handle_size) / 2.0))
-                )
-                .into(),
-            ))
+            px(container_height.mul_add(slider_position, -(size / 2.0)))
         },
     );
     slider
 }
 
 fn left_image(
-    horizontal: bool,
+    orientation: Orientation,
     container_width: f64,
     slider_position: f64,
     container_height: f64,
-    left_image_css: Option<HashMap<String, String>>,
+    (object_fit, object_position): (ObjectFit, ObjectPosition),
+    viewport: Viewport,
 ) -> Style {
-    let mut left_image = [
-        ("display", "block"),
-        ("height", "100%"),
-        ("object-fit", "cover"),
-        ("position", "absolute"),
-        ("width", "100%"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    });
-
-    left_image.insert(
-        "clip".to_string(),
-        if horizontal {
-            Some(Attribute::String(
-                format!(
-                    "rect(auto, {:.0}px, auto, auto)",
-                    container_width * slider_position
-                )
-                .into(),
-            ))
-        } else {
-            Some(Attribute::String(
-                format!(
-                    "rect(auto, auto, {:.0}px, auto)",
-                    container_height * slider_position
-                )
-                .into(),
-            ))
-        },
+    let mut left_image = Style::new()
+        .with(Property::Display, "block")
+        .with(Property::Height, percent(100.0))
+        .with(Property::ObjectFit, object_fit)
+        .with(Property::ObjectPosition, object_position)
+        .with(Property::Position, "absolute")
+        .with(Property::Transform, transform(viewport))
+        .with(Property::TransformOrigin, (px(0.0), px(0.0)))
+        .with(Property::Width, percent(100.0));
+
+    left_image.set(
+        Property::Clip,
+        clip_rect(
+            orientation.is_horizontal(),
+            if orientation.is_horizontal() {
+                container_width.mul_add(slider_position, viewport.x) / viewport.zoom
+            } else {
+                container_height.mul_add(slider_position, viewport.y) / viewport.zoom
+            },
+            !orientation.is_reversed(),
+        ),
     );
 
-    if let Some(mut left_image_css) = left_image_css {
-        left_image.keys().for_each(|key| {
-            left_image_css.remove(key);
-        });
-
-        for (key, value) in left_image_css {
-            left_image.insert(key, Some(value.into_attribute()));
-        }
-    }
-
     left_image
 }
 
 fn right_image(
-    horizontal: bool,
+    orientation: Orientation,
     container_width: f64,
     slider_position: f64,
     container_height: f64,
-    right_image_css: Option<HashMap<String, String>>,
+    (object_fit, object_position): (ObjectFit, ObjectPosition),
+    viewport: Viewport,
 ) -> Style {
-    let mut right_image = [
-        ("display", "block"),
-        ("height", "100%"),
-        ("object-fit", "cover"),
-        ("position", "absolute"),
-        ("width", "100%"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
+    let mut right_image = Style::new()
+        .with(Property::Display, "block")
+        .with(Property::Height, percent(100.0))
+        .with(Property::ObjectFit, object_fit)
+        .with(Property::ObjectPosition, object_position)
+        .with(Property::Position, "absolute")
+        .with(Property::Transform, transform(viewport))
+        .with(Property::TransformOrigin, (px(0.0), px(0.0)))
+        .with(Property::Width, percent(100.0));
+
+    right_image.set(
+        Property::Clip,
+        clip_rect(
+            orientation.is_horizontal(),
+            if orientation.is_horizontal() {
+                container_width.mul_add(slider_position, viewport.x) / viewport.zoom
+            } else {
+                container_height.mul_add(slider_position, viewport.y) / viewport.zoom
+            },
+            orientation.is_reversed(),
+        ),
+    );
+
+    right_image
+}
+
+/// Dims the label of the image that is currently hidden by blinking.
+fn highlight_label(label: &mut Style, active: bool) {
+    label.set(Property::Opacity, if active { 1.0 } else { 0.4 });
+    if active {
+        label.set(
+            Property::Outline,
+            Value::Stroke(px(2.0), "solid", Color::WHITE),
         );
-        acc
-    });
+    } else {
+        label.set(Property::Outline, "none");
+    }
+}
 
-    right_image.insert(
-        "clip".to_string(),
-        if horizontal {
-            Some(Attribute::String(
-                format!(
-                    "rect(auto, auto, auto, {:.0}px)",
-                    container_width * slider_position
-                )
-                .into(),
-            ))
-        } else {
-            Some(Attribute::String(
-                format!(
-                    "rect({:.0}px, auto, auto, auto)",
-                    container_height * slider_position
-                )
-                .into(),
-            ))
-        },
+/// Clips an element to a polygon given in container pixels, mapped through
+/// the zoom and pan the element is transformed with.
+fn clip_polygon(element: &mut Style, polygon: &[(f64, f64)], viewport: Viewport) {
+    let points = polygon
+        .iter()
+        .map(|(x, y)| {
+            (
+                px((x + viewport.x) / viewport.zoom),
+                px((y + viewport.y) / viewport.zoom),
+            )
+        })
+        .collect();
+
+    element.remove(Property::Clip);
+    element.set(Property::ClipPath, Value::Polygon(points));
+}
+
+/// Centers the slider on the split and turns it to `angle` degrees, long
+/// enough to cross the container in any direction.
+fn rotate_slider(slider: &mut Style, angle: f64, (x, y): (f64, f64), length: f64) {
+    slider.set(Property::Height, px(length));
+    slider.set(Property::Left, px(x));
+    slider.set(Property::Top, px(y));
+    slider.set(
+        Property::Transform,
+        Transform::translate(percent(-50.0), percent(-50.0)).rotate(angle),
     );
+}
 
-    if let Some(mut right_image_css) = right_image_css {
-        right_image.keys().for_each(|key| {
-            right_image_css.remove(key);
-        });
+/// Confines an image to its pane, clipping whatever zoom and pan push
+/// beyond it.
+fn fit_pane(image: &mut Style, pane: Pane, viewport: Viewport) {
+    image.set(Property::Height, px(pane.height));
+    image.set(Property::Left, px(pane.x));
+    image.set(Property::Top, px(pane.y));
+    image.set(Property::Width, px(pane.width));
+    image.set(
+        Property::Clip,
+        Value::Rect([
+            px(viewport.y / viewport.zoom),
+            px((pane.width + viewport.x) / viewport.zoom),
+            px((pane.height + viewport.y) / viewport.zoom),
+            px(viewport.x / viewport.zoom),
+        ]),
+    );
+}
 
-        for (key, value) in right_image_css {
-            right_image.insert(key, Some(Attribute::String(value.into())));
-        }
+/// `clip` keeping what lies before `split` pixels from the left or top edge,
+/// or what lies after it.
+fn clip_rect(horizontal: bool, split: f64, before: bool) -> Value {
+    let split = px(split);
+    let auto = Length::Auto;
+
+    Value::Rect(match (horizontal, before) {
+        (true, true) => [auto, split, auto, auto],
+        (true, false) => [auto, auto, auto, split],
+        (false, true) => [auto, auto, split, auto],
+        (false, false) => [split, auto, auto, auto],
+    })
+}
+
+fn overlay_image(right_image: &Style) -> Style {
+    right_image.clone().with(Property::PointerEvents, "none")
+}
+
+fn transform(viewport: Viewport) -> Transform {
+    Transform::translate(px(-viewport.x), px(-viewport.y)).scale(viewport.zoom)
+}
+
+/// Element shown fullscreen, centering the comparison on a black
+/// background there.
+fn frame(fullscreen: bool) -> Style {
+    let frame = Style::new().with(Property::Position, "relative");
+
+    if fullscreen {
+        frame
+            .with(Property::AlignItems, "center")
+            .with(Property::Background, Color::Named("black"))
+            .with(Property::Display, "flex")
+            .with(Property::JustifyContent, "center")
+    } else {
+        frame.with(Property::Display, "block")
     }
-    right_image
 }
 
-fn container(container_height: f64) -> Style {
-    [
-        ("box-sizing", "border-box"),
-        ("position", "relative"),
-        ("width", "100%"),
-        ("height", format!("{container_height:.0}px").as_str()),
-        ("overflow", "hidden"),
-    ]
-    .iter()
-    .fold(style::Style::default(), |mut acc, (key, value)| {
-        acc.insert(
-            (*key).to_string(),
-            Some(Attribute::String((*value).to_string().into())),
-        );
-        acc
-    })
+/// The comparison itself, `fullscreen_width` wide when it is fitted to the
+/// screen.
+fn container(container_height: f64, fullscreen_width: Option<f64>) -> Style {
+    Style::new()
+        .with(Property::BoxSizing, "border-box")
+        .with(Property::Position, "relative")
+        .with(Property::Width, fullscreen_width.map_or(percent(100.0), px))
+        .with(Property::Height, px(container_height))
+        .with(Property::Overflow, "hidden")
 }